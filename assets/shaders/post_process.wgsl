@@ -0,0 +1,57 @@
+struct PostProcessSettings {
+    flash_color: vec4<f32>,
+    grade_tint: vec4<f32>,
+    vignette: f32,
+    flash: f32,
+    crt: f32,
+    saturation: f32,
+    contrast: f32,
+    brightness: f32,
+    time: f32,
+    resolution_y: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> settings: PostProcessSettings;
+@group(1) @binding(1)
+var source: texture_2d<f32>;
+@group(1) @binding(2)
+var source_sampler: sampler;
+
+struct FragmentInput {
+    #import bevy_sprite::mesh2d_vertex_output
+};
+
+fn crt_uv(uv: vec2<f32>) -> vec2<f32> {
+    let centered = uv * 2.0 - 1.0;
+    let bent = centered + centered.yx * centered.yx * centered * 0.08 * settings.crt;
+    return bent * 0.5 + 0.5;
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    let uv = crt_uv(in.uv);
+    let inside = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0));
+
+    let shift = vec2<f32>(0.0015 * settings.crt, 0.0);
+    var color = vec3<f32>(
+        textureSampleLevel(source, source_sampler, uv + shift, 0.0).r,
+        textureSampleLevel(source, source_sampler, uv, 0.0).g,
+        textureSampleLevel(source, source_sampler, uv - shift, 0.0).b,
+    );
+
+    let scanline = sin((uv.y * settings.resolution_y + settings.time * 4.0) * 3.14159) * 0.5 + 0.5;
+    color = color * mix(1.0, 0.75 + 0.25 * scanline, settings.crt);
+
+    let luma = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luma), color, settings.saturation);
+    color = (color - 0.5) * settings.contrast + 0.5 + settings.brightness;
+    color = color * settings.grade_tint.rgb;
+
+    let vignette = smoothstep(0.3, 0.75, distance(in.uv, vec2<f32>(0.5))) * settings.vignette;
+    color = mix(color, vec3<f32>(0.6, 0.0, 0.0), vignette);
+
+    color = mix(color, settings.flash_color.rgb, settings.flash * settings.flash_color.a);
+
+    return vec4<f32>(select(vec3<f32>(0.0), color, inside), 1.0);
+}
@@ -0,0 +1,162 @@
+use crate::prelude::*;
+use bevy::input::InputSystem;
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .add_system(
+                update_action_state
+                    .in_set(ActionSystem)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem),
+            );
+    }
+}
+
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct ActionSystem;
+
+const STICK_PRESS_THRESHOLD: f32 = 0.5;
+
+impl Default for InputBindings {
+    fn default() -> InputBindings {
+        use GamepadButtonType as Pad;
+        use InputBinding::{Gamepad, Key};
+
+        InputBindings(HashMap::from([
+            (
+                Action::MoveUp,
+                vec![Key(KeyCode::W), Key(KeyCode::Up), Gamepad(Pad::DPadUp)],
+            ),
+            (
+                Action::MoveDown,
+                vec![Key(KeyCode::S), Key(KeyCode::Down), Gamepad(Pad::DPadDown)],
+            ),
+            (
+                Action::MoveLeft,
+                vec![Key(KeyCode::A), Key(KeyCode::Left), Gamepad(Pad::DPadLeft)],
+            ),
+            (
+                Action::MoveRight,
+                vec![
+                    Key(KeyCode::D),
+                    Key(KeyCode::Right),
+                    Gamepad(Pad::DPadRight),
+                ],
+            ),
+            (Action::Attack, vec![Key(KeyCode::J), Gamepad(Pad::West)]),
+            (
+                Action::Dash,
+                vec![Key(KeyCode::LShift), Gamepad(Pad::RightTrigger)],
+            ),
+            (
+                Action::Pause,
+                vec![Key(KeyCode::Escape), Gamepad(Pad::Start)],
+            ),
+            (
+                Action::Confirm,
+                vec![
+                    Key(KeyCode::Return),
+                    Key(KeyCode::Space),
+                    Gamepad(Pad::South),
+                ],
+            ),
+            (Action::Cancel, vec![Key(KeyCode::Back), Gamepad(Pad::East)]),
+        ]))
+    }
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.pressed(action) && !self.previous.contains(&action)
+    }
+}
+
+fn update_action_state(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    bindings: Res<InputBindings>,
+    rebind: Res<RebindState>,
+    mut state: ResMut<ActionState>,
+) {
+    let binding_pressed = |binding: &InputBinding| match *binding {
+        InputBinding::Key(key) => keyboard_input.pressed(key),
+        InputBinding::Gamepad(button) => gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.pressed(GamepadButton::new(gamepad, button))),
+    };
+
+    let read_stick = |x: GamepadAxisType, y: GamepadAxisType| {
+        gamepads
+            .iter()
+            .map(|gamepad| {
+                let axis = |axis_type| {
+                    gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis_type))
+                        .unwrap_or(0.0)
+                };
+                Vec2::new(axis(x), axis(y))
+            })
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+            .unwrap_or(Vec2::ZERO)
+            .clamp_length_max(1.0)
+    };
+    let aim = read_stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY);
+    let stick = read_stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY);
+
+    let mut pressed: Vec<Action> = bindings
+        .0
+        .iter()
+        .filter(|(_, bindings)| bindings.iter().any(binding_pressed))
+        .map(|(action, _)| *action)
+        .collect();
+
+    let axis = |negative: Action, positive: Action| {
+        let value = |action| if pressed.contains(&action) { 1.0 } else { 0.0 };
+        value(positive) - value(negative)
+    };
+    let digital = Vec2::new(
+        axis(Action::MoveLeft, Action::MoveRight),
+        axis(Action::MoveDown, Action::MoveUp),
+    )
+    .normalize_or_zero();
+
+    for (action, held) in [
+        (Action::MoveUp, stick.y > STICK_PRESS_THRESHOLD),
+        (Action::MoveDown, stick.y < -STICK_PRESS_THRESHOLD),
+        (Action::MoveLeft, stick.x < -STICK_PRESS_THRESHOLD),
+        (Action::MoveRight, stick.x > STICK_PRESS_THRESHOLD),
+    ] {
+        if held && !pressed.contains(&action) {
+            pressed.push(action);
+        }
+    }
+
+    state.movement = if stick.length_squared() > digital.length_squared() {
+        stick
+    } else {
+        digital
+    };
+    state.aim = aim;
+    let previous = std::mem::replace(&mut state.pressed, pressed);
+
+    // While a binding is being captured nothing counts as freshly pressed, so the
+    // captured input doesn't also trigger its action once listening stops.
+    if rebind.listening.is_some() {
+        state.movement = Vec2::ZERO;
+        state.aim = Vec2::ZERO;
+        state.previous = state.pressed.clone();
+    } else {
+        state.previous = previous;
+    }
+}
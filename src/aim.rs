@@ -0,0 +1,73 @@
+use crate::{attacks::blade_attack_facing, camera::WorldToScreen, prelude::*};
+
+pub struct AimPlugin;
+
+impl Plugin for AimPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Aim>().add_system(
+            update_aim
+                .before(blade_attack_facing)
+                .in_set(OnUpdate(GameState::GamePlay)),
+        );
+    }
+}
+
+const STICK_AIM_THRESHOLD: f32 = 0.3;
+
+fn update_aim(
+    mut aim: ResMut<Aim>,
+    actions: Res<ActionState>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    player: Query<&GlobalTransform, With<Player>>,
+    world_to_screen: WorldToScreen,
+) {
+    let stick = stick_direction(actions.aim);
+    if stick.is_some() {
+        aim.device = AimDevice::Stick;
+    } else if cursor_moved.iter().count() > 0 {
+        aim.device = AimDevice::Mouse;
+    }
+
+    // Both devices keep the last direction when they stop pointing somewhere,
+    // so releasing the stick or leaving the window doesn't snap the weapon back.
+    let direction = match aim.device {
+        AimDevice::Stick => stick,
+        AimDevice::Mouse => {
+            let (Ok(window), Ok(player)) = (window.get_single(), player.get_single()) else {
+                return;
+            };
+            window
+                .cursor_position()
+                .map(|cursor| Vec2::new(cursor.x, window.height() - cursor.y))
+                .and_then(|cursor| world_to_screen.unproject(cursor))
+                .and_then(|target| (target - player.translation().truncate()).try_normalize())
+        }
+    };
+    if direction.is_some() {
+        aim.direction = direction;
+    }
+}
+
+fn stick_direction(stick: Vec2) -> Option<Vec2> {
+    (stick.length() > STICK_AIM_THRESHOLD).then(|| stick.normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stick_inside_the_dead_zone_does_not_aim() {
+        assert_eq!(stick_direction(Vec2::ZERO), None);
+        assert_eq!(stick_direction(Vec2::new(0.29, 0.0)), None);
+        assert_eq!(stick_direction(Vec2::new(0.2, -0.2)), None);
+    }
+
+    #[test]
+    fn stick_outside_the_dead_zone_aims_along_it() {
+        assert_eq!(stick_direction(Vec2::new(0.31, 0.0)), Some(Vec2::X));
+        let direction = stick_direction(Vec2::new(-0.3, -0.3)).unwrap();
+        assert!((direction - Vec2::new(-1.0, -1.0).normalize()).length() < 1e-5);
+    }
+}
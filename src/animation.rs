@@ -71,18 +71,12 @@ pub fn change_player_animation(
     let (mut atlas, mut animation, mut sprite) = player.single_mut();
     let check_face = player_face.single();
 
-    let set = if check_face.facing == Facing::Right && check_face.state == PlayerState::Moving {
-        Animation::PlayerRight
-    } else if check_face.facing == Facing::Up && check_face.state == PlayerState::Moving {
-        Animation::PlayerUp
-    } else if check_face.facing == Facing::Left && check_face.state == PlayerState::Moving {
-        Animation::PlayerLeft
-    } else if check_face.facing == Facing::Down && check_face.state == PlayerState::Moving {
-        Animation::PlayerDown
-    } else if check_face.state == PlayerState::Idle {
-        Animation::PlayerIdle
-    } else {
-        Animation::PlayerIdle
+    let set = match (check_face.state, check_face.facing) {
+        (PlayerState::Idle, _) => Animation::PlayerIdle,
+        (_, Facing::Right | Facing::UpRight | Facing::DownRight) => Animation::PlayerRight,
+        (_, Facing::Left | Facing::UpLeft | Facing::DownLeft) => Animation::PlayerLeft,
+        (_, Facing::Up) => Animation::PlayerUp,
+        (_, Facing::Down) => Animation::PlayerDown,
     };
 
     let Some((new_atlas, new_animaiton)) = animaitons.get(set) else {error!("No Animation Jump Loaded"); return;};
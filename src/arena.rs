@@ -0,0 +1,299 @@
+use crate::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    ecs::system::SystemParam,
+    utils::BoxedFuture,
+};
+
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Arena>()
+            .init_asset_loader::<ArenaLoader>()
+            .add_startup_system(load_arena)
+            .add_system(spawn_arena.in_set(OnUpdate(GameState::GamePlay)));
+    }
+}
+
+// Enemies never spawn closer to the player than this.
+const SPAWN_MIN_DISTANCE: f32 = 10.0;
+
+#[derive(SystemParam)]
+pub struct ArenaLayout<'w> {
+    current: Res<'w, CurrentArena>,
+    arenas: Res<'w, Assets<Arena>>,
+}
+
+impl<'w> ArenaLayout<'w> {
+    pub fn get(&self) -> Option<&Arena> {
+        self.arenas.get(&self.current.0)
+    }
+}
+
+#[derive(Default)]
+pub struct ArenaLoader;
+
+impl AssetLoader for ArenaLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let arena = Arena::parse(std::str::from_utf8(bytes)?)?;
+            load_context.set_default_asset(LoadedAsset::new(arena));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["arena"]
+    }
+}
+
+impl Arena {
+    pub fn parse(source: &str) -> Result<Arena, bevy::asset::Error> {
+        let rows: Vec<&str> = source
+            .lines()
+            .map(str::trim_end)
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return Err(bevy::asset::Error::msg("arena layout is empty"));
+        }
+
+        let mut tiles = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, glyph) in row
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(width)
+                .enumerate()
+            {
+                tiles.push(match glyph {
+                    '.' | ' ' => ArenaTile::Floor,
+                    '#' => ArenaTile::Wall,
+                    'o' => ArenaTile::Obstacle,
+                    'S' => ArenaTile::SpawnPoint,
+                    'P' => ArenaTile::PickupPoint,
+                    '@' => ArenaTile::PlayerStart,
+                    other => {
+                        return Err(bevy::asset::Error::msg(format!(
+                            "unknown arena tile '{other}' at {x},{y}"
+                        )))
+                    }
+                });
+            }
+        }
+
+        Ok(Arena {
+            width: width as i32,
+            height: rows.len() as i32,
+            tiles,
+        })
+    }
+
+    pub fn get(&self, tile: IVec2) -> Option<ArenaTile> {
+        if tile.x < 0 || tile.y < 0 || tile.x >= self.width || tile.y >= self.height {
+            return None;
+        }
+        Some(self.tiles[(tile.y * self.width + tile.x) as usize])
+    }
+
+    pub fn is_walkable(&self, tile: IVec2) -> bool {
+        !matches!(
+            self.get(tile),
+            None | Some(ArenaTile::Wall) | Some(ArenaTile::Obstacle)
+        )
+    }
+
+    pub fn tile_to_world(&self, tile: IVec2) -> Vec2 {
+        let origin = Vec2::new(
+            -(self.width - 1) as f32 / 2.0,
+            (self.height - 1) as f32 / 2.0,
+        );
+        (origin + Vec2::new(tile.x as f32, -tile.y as f32)) * ARENA_TILE_SIZE
+    }
+
+    pub fn world_to_tile(&self, position: Vec2) -> IVec2 {
+        let local = position / ARENA_TILE_SIZE
+            + Vec2::new(
+                (self.width - 1) as f32 / 2.0,
+                -(self.height - 1) as f32 / 2.0,
+            );
+        IVec2::new(local.x.round() as i32, -local.y.round() as i32)
+    }
+
+    pub fn cells(&self, kind: ArenaTile) -> impl Iterator<Item = IVec2> + '_ {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(move |(_, tile)| **tile == kind)
+            .map(move |(i, _)| IVec2::new(i as i32 % width, i as i32 / width))
+    }
+
+    pub fn bounds(&self) -> Rect {
+        let half_tile = Vec2::splat(ARENA_TILE_SIZE / 2.0);
+        Rect::from_corners(
+            self.tile_to_world(IVec2::new(0, self.height - 1)) - half_tile,
+            self.tile_to_world(IVec2::new(self.width - 1, 0)) + half_tile,
+        )
+    }
+
+    pub fn player_start(&self) -> Vec2 {
+        self.cells(ArenaTile::PlayerStart)
+            .next()
+            .map(|tile| self.tile_to_world(tile))
+            .unwrap_or(Vec2::ZERO)
+    }
+
+    pub fn nearest_floor(&self, position: Vec2) -> Vec2 {
+        let target = self
+            .world_to_tile(position)
+            .clamp(IVec2::ZERO, IVec2::new(self.width - 1, self.height - 1));
+        let nearest = (0..self.width.max(self.height)).find_map(|radius| {
+            (-radius..=radius)
+                .flat_map(|x| (-radius..=radius).map(move |y| IVec2::new(x, y)))
+                .filter(|offset| offset.x.abs() == radius || offset.y.abs() == radius)
+                .map(|offset| target + offset)
+                .find(|tile| self.is_walkable(*tile))
+        });
+        self.tile_to_world(nearest.unwrap_or(target))
+    }
+
+    pub fn spawn_position(&self, global_rng: &mut GlobalRng, target: Vec2, player: Vec2) -> Vec2 {
+        let spawn_points: Vec<Vec2> = self
+            .cells(ArenaTile::SpawnPoint)
+            .map(|tile| self.tile_to_world(tile))
+            .filter(|position| {
+                // Half a tile of slack keeps the jittered position inside the despawn distance.
+                let distance = position.distance(player);
+                distance > SPAWN_MIN_DISTANCE
+                    && distance < ENEMY_DESPAWN_DISTANCE - ARENA_TILE_SIZE / 2.0
+            })
+            .collect();
+        if spawn_points.is_empty() {
+            return self.nearest_floor(target);
+        }
+
+        // Spread the wave over the spawn point's tile so it doesn't arrive as a single stack.
+        let jitter = Vec2::new(global_rng.f32_normalized(), global_rng.f32_normalized());
+        spawn_points[global_rng.usize(0..spawn_points.len())] + jitter * ARENA_TILE_SIZE * 0.25
+    }
+
+    pub fn pickup_position(&self, target: Vec2) -> Vec2 {
+        self.cells(ArenaTile::PickupPoint)
+            .map(|tile| self.tile_to_world(tile))
+            .min_by(|a, b| a.distance(target).total_cmp(&b.distance(target)))
+            .unwrap_or_else(|| self.nearest_floor(target))
+    }
+}
+
+// Headless runs have to play the same arena from the first frame, wherever they are launched from.
+fn load_arena(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut arenas: ResMut<Assets<Arena>>,
+    headless: Option<Res<Headless>>,
+) {
+    let arena = match headless {
+        Some(_) => arenas.add(
+            Arena::parse(include_str!("../assets/arenas/default.arena"))
+                .expect("built-in arena is valid"),
+        ),
+        None => assets.load("arenas/default.arena"),
+    };
+    commands.insert_resource(CurrentArena(arena));
+}
+
+fn spawn_arena(mut commands: Commands, arena: ArenaLayout, blocks: Query<(), With<ArenaBlock>>) {
+    if !blocks.is_empty() {
+        return;
+    }
+    let Some(arena) = arena.get() else {
+        return;
+    };
+
+    for y in 0..arena.height {
+        for x in 0..arena.width {
+            let tile = IVec2::new(x, y);
+            let (color, size) = match arena.get(tile) {
+                Some(ArenaTile::Wall) => (Color::rgb(0.25, 0.22, 0.2), ARENA_TILE_SIZE),
+                Some(ArenaTile::Obstacle) => (Color::rgb(0.45, 0.3, 0.15), ARENA_TILE_SIZE * 0.8),
+                _ => continue,
+            };
+
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(arena.tile_to_world(tile).extend(5.0)),
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::splat(size)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                RigidBody::Fixed,
+                Collider::cuboid(size / 2.0, size / 2.0),
+                ArenaBlock,
+                GamePlayEntity,
+                Name::new("Arena Block"),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_arena() -> Arena {
+        Arena::parse(include_str!("../assets/arenas/default.arena")).unwrap()
+    }
+
+    #[test]
+    fn enemies_spawn_at_varied_points_around_the_player_start() {
+        let arena = default_arena();
+        let player = arena.player_start();
+        let mut global_rng = GlobalRng::with_seed(11);
+
+        let mut spawn_points = Vec::new();
+        for _ in 0..100 {
+            let position = arena.spawn_position(&mut global_rng, player + Vec2::X * 22.0, player);
+            let distance = position.distance(player);
+            assert!(distance > SPAWN_MIN_DISTANCE - ARENA_TILE_SIZE);
+            assert!(distance < ENEMY_DESPAWN_DISTANCE);
+
+            let tile = arena.world_to_tile(position);
+            assert_eq!(arena.get(tile), Some(ArenaTile::SpawnPoint));
+            if !spawn_points.contains(&tile) {
+                spawn_points.push(tile);
+            }
+        }
+        assert!(spawn_points.len() > 1);
+    }
+
+    #[test]
+    fn enemies_spawn_within_despawn_distance_anywhere_in_the_arena() {
+        let arena = default_arena();
+        let mut global_rng = GlobalRng::with_seed(5);
+
+        for tile in arena.cells(ArenaTile::Floor) {
+            let player = arena.tile_to_world(tile);
+            for direction in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+                let position =
+                    arena.spawn_position(&mut global_rng, player + direction * 22.0, player);
+                assert!(position.distance(player) < ENEMY_DESPAWN_DISTANCE);
+                assert!(arena.is_walkable(arena.world_to_tile(position)));
+            }
+        }
+    }
+}
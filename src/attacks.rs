@@ -1,4 +1,8 @@
-use crate::{prelude::*, ui::spawn_world_text};
+use crate::{
+    prelude::*,
+    props::hit_prop,
+    targeting::{rebuild_enemy_grid, Targeting},
+};
 use std::time::Duration;
 
 pub struct AttackPlugin;
@@ -6,25 +10,37 @@ pub struct AttackPlugin;
 impl Plugin for AttackPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            (player_attack, blade_attack, blade_attack_facing)
+            (
+                player_attack,
+                blade_attack,
+                blade_attack_facing,
+                throw_daggers.after(rebuild_enemy_grid),
+                move_daggers,
+                dagger_hits.after(move_daggers),
+            )
                 .in_set(OnUpdate(GameState::GamePlay)),
         );
     }
 }
 
 fn damage_enemy(
-    commands: &mut Commands,
-    assets: &AssetServer,
+    damage_numbers: &mut EventWriter<DamageNumberEvent>,
+    entity: Entity,
     enemy: &mut Enemy,
+    elite: Option<Mut<Elite>>,
     position: &Transform,
     damage: f32,
 ) {
-    spawn_world_text(
-        commands,
-        assets,
-        position.translation.truncate(),
-        &format!("{:?}", damage as i32),
-    );
+    let damage = match elite {
+        Some(mut elite) => elite.absorb(damage),
+        None => damage,
+    };
+
+    damage_numbers.send(DamageNumberEvent {
+        target: Some(entity),
+        position: position.translation,
+        text: DamageText::Amount(damage),
+    });
 
     enemy.health -= damage;
 }
@@ -71,7 +87,7 @@ fn player_attack(
     }
 }
 
-pub fn spawn_blade(commands: &mut Commands, assets: &AssetServer) -> Entity {
+pub fn spawn_blade(commands: &mut Commands, assets: &GameAssets) -> Entity {
     let mut timer = Timer::from_seconds(1.0, TimerMode::Repeating);
     timer.set_elapsed(Duration::from_secs(1));
 
@@ -105,35 +121,45 @@ pub fn spawn_blade(commands: &mut Commands, assets: &AssetServer) -> Entity {
 pub fn blade_attack_facing(
     mut blade_query: Query<(&mut Transform, &mut Sprite), With<Blade>>,
     player: Query<&Player>,
+    settings: Res<UserSettings>,
+    aim: Res<Aim>,
 ) {
     let player = player.single();
 
-    if let Ok((mut blade, mut sprite)) = blade_query.get_single_mut() {
-        blade.translation = match player.facing {
-            Facing::Left => {
-                sprite.flip_x = false;
-                Vec3::new(-2.0, 0.0, 0.0)
-            }
-            Facing::Right => {
-                sprite.flip_x = true;
-                Vec3::new(2.0, 0.0, 0.0)
-            }
-            Facing::Up => {
-                sprite.flip_x = true;
-                Vec3::new(0.0, 2.0, 0.0)
-            }
-            Facing::Down => {
-                sprite.flip_x = false;
-                Vec3::new(0.0, -2.0, 0.0)
-            }
-        };
+    let Ok((mut blade, mut sprite)) = blade_query.get_single_mut() else {
+        return;
+    };
+
+    match (settings.aim_mode, aim.direction) {
+        (AimMode::Free, Some(direction)) => {
+            // The sprite points right when flipped, so aiming left un-flips it
+            // instead of rotating it upside down.
+            let angle = direction.y.atan2(direction.x);
+            sprite.flip_x = direction.x >= 0.0;
+            let rotation = if sprite.flip_x {
+                angle
+            } else {
+                angle - std::f32::consts::PI
+            };
+            blade.translation = (direction * 2.0).extend(0.0);
+            blade.rotation = Quat::from_rotation_z(rotation);
+        }
+        _ => {
+            blade.translation = (player.facing.direction() * 2.0).extend(0.0);
+            blade.rotation = Quat::IDENTITY;
+            sprite.flip_x = matches!(
+                player.facing,
+                Facing::Right | Facing::UpRight | Facing::DownRight | Facing::Up
+            );
+        }
     }
 }
 
-fn blade_attack(
-    mut commands: Commands,
-    assets: Res<AssetServer>,
-    audio: Res<Audio>,
+#[allow(clippy::too_many_arguments)]
+pub fn blade_attack(
+    mut sfx: EventWriter<PlaySfx>,
+    mut shake: EventWriter<ScreenShake>,
+    mut damage_numbers: EventWriter<DamageNumberEvent>,
     mut blades: Query<(
         &Collider,
         &GlobalTransform,
@@ -141,11 +167,17 @@ fn blade_attack(
         &mut TwoFrameAnimation,
         &mut Visibility,
     )>,
-    mut enemy: Query<(&mut Enemy, &Transform)>,
+    mut enemy: Query<(&mut Enemy, &Transform, Option<&mut Elite>)>,
+    mut props: Query<&mut Prop>,
     rapier_context: Res<RapierContext>,
+    actions: Res<ActionState>,
     time: Res<Time>,
 ) {
     for (collider, transform, mut blade, mut animation, mut visibility) in &mut blades {
+        if actions.just_pressed(Action::Attack) && blade.timer.percent() > 0.5 {
+            let duration = blade.timer.duration();
+            blade.timer.set_elapsed(duration);
+        }
         blade.timer.tick(time.delta());
 
         *visibility = if blade.timer.percent() < 0.2 || blade.timer.percent() > 0.9 {
@@ -166,14 +198,261 @@ fn blade_attack(
                 collider,
                 QueryFilter::new(),
                 |entity| {
-                    if let Ok((mut enemy, transform)) = enemy.get_mut(entity) {
-                        damage_enemy(&mut commands, &assets, &mut enemy, transform, blade.damage);
-                        let sound_effect = assets.load("sounds/knifeSlice.ogg");
-                        audio.play(sound_effect);
+                    if let Ok((mut enemy, transform, elite)) = enemy.get_mut(entity) {
+                        damage_enemy(
+                            &mut damage_numbers,
+                            entity,
+                            &mut enemy,
+                            elite,
+                            transform,
+                            blade.damage,
+                        );
+                        sfx.send(PlaySfx::at(Sfx::Slice, transform.translation));
+                        if blade.damage >= HEAVY_HIT_DAMAGE {
+                            shake.send(ScreenShake(0.3));
+                        }
                     }
+                    hit_prop(&mut props, entity, blade.damage);
                     true
                 },
             );
         }
     }
 }
+
+const DAGGER_LIFETIME: f32 = 1.0;
+
+fn throw_daggers(
+    mut commands: Commands,
+    mut throwers: Query<(&GlobalTransform, &mut DaggerThrower)>,
+    mut targeting: Targeting,
+    assets: GameAssets,
+    time: Res<Time>,
+) {
+    for (transform, mut thrower) in &mut throwers {
+        thrower.timer.tick(time.delta());
+        if !thrower.timer.just_finished() {
+            continue;
+        }
+
+        let origin = transform.translation().truncate();
+        let Some(target) = targeting.pick(thrower.strategy, origin, thrower.range) else {
+            continue;
+        };
+        let Some(direction) = (target.position - origin).try_normalize() else {
+            continue;
+        };
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(origin.extend(3.0))
+                    .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+                texture: assets.load("sprites/GuttyKreum_1.png"),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(24.0 * PIXEL_TO_WORLD)),
+                    flip_x: true,
+                    ..default()
+                },
+                ..default()
+            },
+            Dagger {
+                velocity: direction * thrower.speed,
+                damage: thrower.damage,
+                lifetime: Timer::from_seconds(DAGGER_LIFETIME, TimerMode::Once),
+                touching: Vec::new(),
+            },
+            Sensor,
+            Collider::ball(0.3),
+            GamePlayEntity,
+            Name::new("Dagger"),
+        ));
+    }
+}
+
+pub fn move_daggers(
+    mut daggers: Query<(Entity, &mut Transform, &mut Dagger, &Collider)>,
+    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+) {
+    for (dagger_entity, mut transform, mut dagger, collider) in &mut daggers {
+        transform.translation += (dagger.velocity * time.delta_seconds()).extend(0.0);
+        dagger.lifetime.tick(time.delta());
+
+        let mut touching = Vec::new();
+        rapier_context.intersections_with_shape(
+            transform.translation.truncate(),
+            0.0,
+            collider,
+            QueryFilter::new().exclude_collider(dagger_entity),
+            |entity| {
+                touching.push(entity);
+                true
+            },
+        );
+        dagger.touching = touching;
+    }
+}
+
+// A dagger is spent on the first wall, prop or enemy it touches.
+pub fn dagger_hits(
+    mut commands: Commands,
+    daggers: Query<(Entity, &Dagger)>,
+    mut enemies: Query<(&mut Enemy, &Transform, Option<&mut Elite>), Without<Dagger>>,
+    mut props: Query<&mut Prop>,
+    walls: Query<(), With<ArenaBlock>>,
+    mut damage_numbers: EventWriter<DamageNumberEvent>,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    for (dagger_entity, dagger) in &daggers {
+        let hit = dagger.touching.iter().any(|&entity| {
+            if walls.contains(entity) || hit_prop(&mut props, entity, dagger.damage) {
+                return true;
+            }
+            let Ok((mut enemy, enemy_transform, elite)) = enemies.get_mut(entity) else {
+                return false;
+            };
+            damage_enemy(
+                &mut damage_numbers,
+                entity,
+                &mut enemy,
+                elite,
+                enemy_transform,
+                dagger.damage,
+            );
+            sfx.send(PlaySfx::at(Sfx::Slice, enemy_transform.translation));
+            true
+        });
+
+        if hit || dagger.lifetime.finished() {
+            commands.entity(dagger_entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{asset::AssetPlugin, time::TimeUpdateStrategy};
+
+    fn thrower_app(enemy_distance: f32) -> App {
+        let mut grid = EnemyGrid::default();
+        grid.insert(TargetCandidate {
+            entity: Entity::from_raw(99),
+            position: Vec2::new(enemy_distance, 0.0),
+            health: 10.0,
+            max_health: 10.0,
+        });
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .insert_resource(Headless)
+            .insert_resource(GlobalRng::with_seed(1))
+            .insert_resource(grid)
+            .add_system(throw_daggers);
+        app.world
+            .spawn((GlobalTransform::default(), DaggerThrower::default()));
+        app
+    }
+
+    fn hits_app() -> App {
+        let mut app = App::new();
+        app.add_event::<DamageNumberEvent>()
+            .add_event::<PlaySfx>()
+            .add_system(dagger_hits);
+        app
+    }
+
+    // Steps time by a tenth of a second per frame; the first frame has no delta.
+    fn update(app: &mut App, frames: usize) {
+        for _ in 0..frames {
+            if let Some(time) = app.world.get_resource::<Time>() {
+                let last = time.last_update().unwrap_or_else(|| time.startup());
+                let next = last + Duration::from_millis(100);
+                app.insert_resource(TimeUpdateStrategy::ManualInstant(next));
+            }
+            app.update();
+        }
+    }
+
+    fn daggers(app: &mut App) -> usize {
+        app.world.query::<&Dagger>().iter(&app.world).count()
+    }
+
+    fn spawn_enemy(app: &mut App) -> Entity {
+        app.world
+            .spawn((
+                Enemy {
+                    speed: 1.0,
+                    health: 10.0,
+                    max_health: 10.0,
+                    damage: 1.0,
+                    asset: "sprites/Kobold.png".to_string(),
+                },
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    fn spawn_dagger(app: &mut App, touching: Vec<Entity>) {
+        app.world.spawn(Dagger {
+            velocity: Vec2::X * DAGGER_SPEED,
+            damage: DAGGER_DAMAGE,
+            lifetime: Timer::from_seconds(DAGGER_LIFETIME, TimerMode::Once),
+            touching,
+        });
+    }
+
+    fn health(app: &App, enemy: Entity) -> f32 {
+        app.world.get::<Enemy>(enemy).unwrap().health
+    }
+
+    #[test]
+    fn daggers_are_thrown_once_per_interval() {
+        let mut app = thrower_app(DAGGER_RANGE - 0.5);
+        update(&mut app, 10);
+        assert_eq!(daggers(&mut app), 0);
+        update(&mut app, 20);
+        assert_eq!(daggers(&mut app), 2);
+    }
+
+    #[test]
+    fn daggers_ignore_enemies_out_of_range() {
+        let mut app = thrower_app(DAGGER_RANGE + 0.5);
+        update(&mut app, 30);
+        assert_eq!(daggers(&mut app), 0);
+    }
+
+    #[test]
+    fn daggers_damage_only_the_first_enemy_once() {
+        let mut app = hits_app();
+        let first = spawn_enemy(&mut app);
+        let second = spawn_enemy(&mut app);
+        spawn_dagger(&mut app, vec![first, second]);
+
+        update(&mut app, 2);
+        assert_eq!(health(&app, first), 10.0 - DAGGER_DAMAGE);
+        assert_eq!(health(&app, second), 10.0);
+        assert_eq!(daggers(&mut app), 0);
+    }
+
+    #[test]
+    fn daggers_stop_at_walls() {
+        let mut app = hits_app();
+        let wall = app.world.spawn(ArenaBlock).id();
+        let enemy = spawn_enemy(&mut app);
+        spawn_dagger(&mut app, vec![wall, enemy]);
+
+        update(&mut app, 1);
+        assert_eq!(health(&app, enemy), 10.0);
+        assert_eq!(daggers(&mut app), 0);
+    }
+
+    #[test]
+    fn daggers_fly_on_until_they_touch_something() {
+        let mut app = hits_app();
+        spawn_dagger(&mut app, Vec::new());
+        update(&mut app, 1);
+        assert_eq!(daggers(&mut app), 1);
+    }
+}
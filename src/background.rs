@@ -0,0 +1,108 @@
+use crate::prelude::*;
+use std::collections::HashSet;
+
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundChunks>()
+            .add_system(seed_background.in_schedule(OnEnter(GameState::GamePlay)))
+            .add_system(stream_background.in_set(OnUpdate(GameState::GamePlay)));
+    }
+}
+
+impl BackgroundChunks {
+    pub fn variant(&self, chunk: IVec2) -> &str {
+        &self.variants[self.variant_index(chunk)]
+    }
+
+    pub fn variant_index(&self, chunk: IVec2) -> usize {
+        let noise = self.noise(chunk.as_vec2() * 0.35);
+        let index = (noise * self.variants.len() as f32) as usize;
+        index.min(self.variants.len() - 1)
+    }
+
+    pub fn chunk_at(&self, position: Vec2) -> IVec2 {
+        (position / self.size).round().as_ivec2()
+    }
+
+    fn noise(&self, point: Vec2) -> f32 {
+        let cell = point.floor();
+        let t = point - cell;
+        let t = t * t * (Vec2::splat(3.0) - 2.0 * t);
+        let cell = cell.as_ivec2();
+
+        let a = self.hash(cell);
+        let b = self.hash(cell + IVec2::X);
+        let c = self.hash(cell + IVec2::Y);
+        let d = self.hash(cell + IVec2::ONE);
+
+        let top = a + (b - a) * t.x;
+        let bottom = c + (d - c) * t.x;
+        top + (bottom - top) * t.y
+    }
+
+    fn hash(&self, cell: IVec2) -> f32 {
+        let mut h = self.seed
+            ^ (cell.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (cell.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        h ^= h >> 33;
+        (h >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn seed_background(mut chunks: ResMut<BackgroundChunks>, mut global_rng: ResMut<GlobalRng>) {
+    chunks.seed = global_rng.u64(..);
+}
+
+fn stream_background(
+    mut commands: Commands,
+    chunks: Res<BackgroundChunks>,
+    camera: Query<&Transform, With<MainCamera>>,
+    tiles: Query<(Entity, &BackgroundTile)>,
+    assets: Res<AssetServer>,
+) {
+    let Ok(camera) = camera.get_single() else {
+        return;
+    };
+
+    let center = chunks.chunk_at(camera.translation.truncate());
+
+    let mut loaded = HashSet::new();
+    for (entity, tile) in &tiles {
+        let offset = tile.chunk - center;
+        if offset.x.abs() > chunks.radius || offset.y.abs() > chunks.radius {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            loaded.insert(tile.chunk);
+        }
+    }
+
+    for i in -chunks.radius..=chunks.radius {
+        for j in -chunks.radius..=chunks.radius {
+            let chunk = center + IVec2::new(i, j);
+            if loaded.contains(&chunk) {
+                continue;
+            }
+
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(
+                        (chunk.as_vec2() * chunks.size).extend(1.0),
+                    ),
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(chunks.size)),
+                        ..default()
+                    },
+                    texture: assets.load(chunks.variant(chunk)),
+                    ..default()
+                },
+                BackgroundTile { chunk },
+                GamePlayEntity,
+                Name::new("Background"),
+            ));
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Runs the game headless at full speed for balance testing.
+//!
+//! cargo run --release --bin simulate -- [--minutes N] [--seed N] [--player idle|kite]
+
+use bevy::time::TimeUpdateStrategy;
+use bevyquest::prelude::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const STEP: f64 = 1.0 / 60.0;
+// How far the kiting player looks for enemies to run from.
+const KITE_RANGE: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlayerScript {
+    Idle,
+    Kite,
+}
+
+#[derive(Resource)]
+struct Simulation {
+    script: PlayerScript,
+    duration: Duration,
+    elapsed: Duration,
+    damage_taken: f32,
+    kills: BTreeMap<String, u32>,
+}
+
+fn main() {
+    let mut minutes = 10.0;
+    let mut seed = None;
+    let mut script = PlayerScript::Idle;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        match (arg.as_str(), value.as_deref()) {
+            ("--minutes", Some(value)) => minutes = value.parse().expect("invalid --minutes"),
+            ("--seed", Some(value)) => seed = Some(value.parse().expect("invalid --seed")),
+            ("--player", Some("idle")) => script = PlayerScript::Idle,
+            ("--player", Some("kite")) => script = PlayerScript::Kite,
+            _ => {
+                eprintln!("usage: simulate [--minutes N] [--seed N] [--player idle|kite]");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let mut config = BevyQuestConfig::default().with_headless(true);
+    if let Some(seed) = seed {
+        config = config.with_seed(seed);
+    }
+
+    App::new()
+        .add_plugins(BevyQuestPlugins(config))
+        .insert_resource(Simulation {
+            script,
+            duration: Duration::from_secs_f64(minutes * 60.0),
+            elapsed: Duration::ZERO,
+            damage_taken: 0.0,
+            kills: BTreeMap::new(),
+        })
+        .add_system(
+            drive_player
+                .in_base_set(CoreSet::PreUpdate)
+                .run_if(in_state(GameState::GamePlay)),
+        )
+        .add_systems((record_damage, record_kills).in_base_set(CoreSet::Last))
+        .set_runner(run_simulation)
+        .run();
+}
+
+fn run_simulation(mut app: App) {
+    let start = Instant::now();
+    let step = Duration::from_secs_f64(STEP);
+
+    loop {
+        let elapsed = {
+            let mut simulation = app.world.resource_mut::<Simulation>();
+            simulation.elapsed += step;
+            simulation.elapsed
+        };
+        app.insert_resource(TimeUpdateStrategy::ManualInstant(start + elapsed));
+        app.update();
+
+        let game_over = app.world.resource::<State<GameState>>().0 == GameState::GameOver;
+        let simulation = app.world.resource::<Simulation>();
+        if game_over || simulation.elapsed >= simulation.duration {
+            report(simulation, game_over);
+            return;
+        }
+    }
+}
+
+fn drive_player(
+    mut actions: ResMut<ActionState>,
+    simulation: Res<Simulation>,
+    grid: Res<EnemyGrid>,
+    player: Query<&Transform, With<Player>>,
+) {
+    actions.movement = match (simulation.script, player.get_single()) {
+        (PlayerScript::Kite, Ok(transform)) => {
+            let position = transform.translation.truncate();
+            grid.within(position, KITE_RANGE)
+                .map(|enemy| {
+                    let away = position - enemy.position;
+                    away.normalize_or_zero() / away.length().max(0.5)
+                })
+                .sum::<Vec2>()
+                .normalize_or_zero()
+        }
+        _ => Vec2::ZERO,
+    };
+}
+
+fn record_damage(mut simulation: ResMut<Simulation>, mut damaged: EventReader<PlayerDamaged>) {
+    simulation.damage_taken += damaged.iter().map(|damaged| damaged.amount).sum::<f32>();
+}
+
+fn record_kills(mut simulation: ResMut<Simulation>, mut killed: EventReader<EnemyKilled>) {
+    for EnemyKilled { asset, .. } in killed.iter() {
+        let kind = Path::new(asset)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| asset.clone());
+        *simulation.kills.entry(kind).or_default() += 1;
+    }
+}
+
+fn report(simulation: &Simulation, game_over: bool) {
+    let survived = simulation.elapsed.as_secs_f32();
+    let outcome = if game_over { "died" } else { "survived" };
+    println!(
+        "{:?} player {outcome} after {}:{:02}",
+        simulation.script,
+        survived as u32 / 60,
+        survived as u32 % 60
+    );
+    println!("damage taken: {:.1}", simulation.damage_taken);
+    println!("kills: {}", simulation.kills.values().sum::<u32>());
+    for (kind, count) in &simulation.kills {
+        println!("  {kind}: {count}");
+    }
+}
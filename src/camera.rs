@@ -1,12 +1,16 @@
-use crate::{player::player_movement, prelude::*};
+use crate::{
+    arena::ArenaLayout, player::player_movement, postprocess::PostProcessMaterial, prelude::*,
+};
 use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    ecs::system::SystemParam,
     prelude::*,
     render::{
         camera::{RenderTarget, ScalingMode},
         render_resource::{
             Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
         },
-        texture::BevyDefault,
+        texture::{BevyDefault, ImageSampler},
         view::RenderLayers,
     },
     sprite::MaterialMesh2dBundle,
@@ -15,10 +19,18 @@ pub struct GameCameraPlugin;
 
 impl Plugin for GameCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(spawn_camera)
-            .add_system(
-                camera_follow
-                    .after(player_movement)
+        app.init_resource::<CameraController>()
+            .init_resource::<CameraShake>()
+            .init_resource::<RenderSettings>()
+            .init_resource::<RenderViewport>()
+            .add_event::<ScreenShake>()
+            .add_startup_system(spawn_camera)
+            .add_system(fit_render_to_window)
+            .add_systems(
+                (
+                    camera_follow.after(player_movement),
+                    camera_shake.after(camera_follow),
+                )
                     .in_set(OnUpdate(GameState::GamePlay)),
             )
             .add_system(camera_zero.in_schedule(OnEnter(GameState::MainMenu)));
@@ -27,30 +39,210 @@ impl Plugin for GameCameraPlugin {
 
 fn camera_follow(
     player: Query<&Transform, (With<Player>, Without<MainCamera>)>,
-    mut camera: Query<&mut Transform, With<MainCamera>>,
+    mut camera: Query<(&mut Transform, &mut CameraRig, &OrthographicProjection), With<MainCamera>>,
+    controller: Res<CameraController>,
+    arena: ArenaLayout,
+    time: Res<Time>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let (mut camera, mut rig, projection) = camera.single_mut();
+    let target = player.translation.truncate();
+    let delta = time.delta_seconds();
+
+    let Some(last_target) = rig.last_target.replace(target) else {
+        rig.focus = target;
+        camera.translation.x = target.x;
+        camera.translation.y = target.y;
+        return;
+    };
+
+    let movement = (target - last_target).normalize_or_zero();
+    let look_ahead_blend = 1.0 - (-controller.smoothing * 0.5 * delta).exp();
+    rig.look_ahead = rig
+        .look_ahead
+        .lerp(movement * controller.look_ahead, look_ahead_blend);
+
+    let mut desired = follow_dead_zone(rig.focus, target + rig.look_ahead, controller.dead_zone);
+
+    if controller.clamp_to_arena {
+        if let Some(arena) = arena.get() {
+            desired = clamp_to_bounds(arena.bounds(), projection.area.half_size(), desired);
+        }
+    }
+
+    let blend = 1.0 - (-controller.smoothing * delta).exp();
+    rig.focus = rig.focus.lerp(desired, blend);
+    camera.translation.x = rig.focus.x;
+    camera.translation.y = rig.focus.y;
+}
+
+fn follow_dead_zone(focus: Vec2, target: Vec2, dead_zone: Vec2) -> Vec2 {
+    let offset = target - focus;
+    let excess = (offset.abs() - dead_zone).max(Vec2::ZERO);
+    focus + excess * offset.signum()
+}
+
+fn clamp_to_bounds(bounds: Rect, half_view: Vec2, position: Vec2) -> Vec2 {
+    let clamp_axis = |min: f32, max: f32, half: f32, value: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) / 2.0
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+    Vec2::new(
+        clamp_axis(bounds.min.x, bounds.max.x, half_view.x, position.x),
+        clamp_axis(bounds.min.y, bounds.max.y, half_view.y, position.y),
+    )
+}
+
+fn camera_shake(
+    mut camera: Query<(&mut Transform, &CameraRig), With<MainCamera>>,
+    mut shake: ResMut<CameraShake>,
+    mut events: EventReader<ScreenShake>,
+    controller: Res<CameraController>,
+    time: Res<Time>,
 ) {
-    if let Ok(player) = player.get_single() {
-        let mut camera = camera.single_mut();
-        camera.translation.x = player.translation.x;
-        camera.translation.y = player.translation.y;
+    for event in events.iter() {
+        shake.trauma = (shake.trauma + event.0).min(1.0);
     }
+    if !controller.shake_enabled {
+        shake.trauma = 0.0;
+    }
+
+    shake.time += time.delta_seconds();
+    let amount = shake.trauma * shake.trauma;
+    let t = shake.time;
+    let noise = Vec3::new(
+        (t * 37.0).sin() * (t * 13.3).cos(),
+        (t * 41.0).cos() * (t * 17.9).sin(),
+        (t * 29.0).sin() * (t * 7.7).cos(),
+    );
+
+    let (mut camera, rig) = camera.single_mut();
+    camera.translation.x = rig.focus.x + noise.x * shake.max_offset * amount;
+    camera.translation.y = rig.focus.y + noise.y * shake.max_offset * amount;
+    camera.rotation = Quat::from_rotation_z(noise.z * shake.max_angle * amount);
+
+    shake.trauma = (shake.trauma - shake.decay * time.delta_seconds()).max(0.0);
 }
 
-fn camera_zero(mut camera: Query<&mut Transform, With<MainCamera>>) {
-    let mut camera = camera.single_mut();
+fn camera_zero(
+    mut camera: Query<(&mut Transform, &mut CameraRig), With<MainCamera>>,
+    mut shake: ResMut<CameraShake>,
+) {
+    let (mut camera, mut rig) = camera.single_mut();
     camera.translation.x = 0.0;
     camera.translation.y = 0.0;
+    camera.rotation = Quat::IDENTITY;
+    *rig = CameraRig::default();
+    shake.trauma = 0.0;
+}
+
+#[derive(SystemParam)]
+pub struct WorldToScreen<'w, 's> {
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+    viewport: Res<'w, RenderViewport>,
+}
+
+impl<'w, 's> WorldToScreen<'w, 's> {
+    pub fn viewport(&self) -> Rect {
+        self.viewport.rect
+    }
+
+    pub fn project(&self, position: Vec3) -> Option<Vec2> {
+        let (camera, transform) = self.camera.get_single().ok()?;
+        let coords = camera.world_to_viewport(transform, position)?;
+        let normalized = coords / camera.logical_viewport_size()?;
+        Some(
+            self.viewport.rect.min
+                + Vec2::new(normalized.x, 1.0 - normalized.y) * self.viewport.rect.size(),
+        )
+    }
+
+    pub fn unproject(&self, screen: Vec2) -> Option<Vec2> {
+        let (camera, transform) = self.camera.get_single().ok()?;
+        let normalized = (screen - self.viewport.rect.min) / self.viewport.rect.size();
+        if normalized.cmplt(Vec2::ZERO).any() || normalized.cmpgt(Vec2::ONE).any() {
+            return None;
+        }
+        let coords =
+            Vec2::new(normalized.x, 1.0 - normalized.y) * camera.logical_viewport_size()?;
+        camera.viewport_to_world_2d(transform, coords)
+    }
+}
+
+impl RenderScaling {
+    pub fn display_size(&self, render: Vec2, window: Vec2, scale_factor: f32) -> Vec2 {
+        match self {
+            RenderScaling::PixelPerfect => {
+                let physical = window * scale_factor;
+                let scale = (physical / render).min_element().floor().max(1.0);
+                render * scale / scale_factor
+            }
+            RenderScaling::Fit => render * (window / render).min_element(),
+            RenderScaling::Stretch => window,
+        }
+    }
+}
+
+fn fit_render_to_window(
+    settings: Res<RenderSettings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut quad: Query<&mut Transform, With<BaseRender>>,
+    main_render: Res<MainRender>,
+    mut images: ResMut<Assets<Image>>,
+    mut viewport: ResMut<RenderViewport>,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+
+    if settings.is_changed() {
+        if let Some(image) = images.get_mut(&main_render.0) {
+            let size = Extent3d {
+                width: settings.width,
+                height: settings.height,
+                ..default()
+            };
+            if image.texture_descriptor.size != size {
+                image.resize(size);
+            }
+            image.sampler_descriptor = match settings.scaling {
+                RenderScaling::PixelPerfect => ImageSampler::nearest(),
+                _ => ImageSampler::linear(),
+            };
+        }
+    }
+
+    let window_size = Vec2::new(window.width(), window.height());
+    let render_size = Vec2::new(settings.width as f32, settings.height as f32);
+    let size =
+        settings
+            .scaling
+            .display_size(render_size, window_size, window.scale_factor() as f32);
+
+    let rect = Rect::from_center_size(window_size / 2.0, size);
+    if viewport.rect != rect {
+        viewport.rect = rect;
+    }
+    for mut transform in &mut quad {
+        transform.scale = size.extend(1.0);
+    }
 }
 
 fn spawn_camera(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut materials: ResMut<Assets<PostProcessMaterial>>,
     mut images: ResMut<Assets<Image>>,
+    settings: Res<RenderSettings>,
 ) {
     let size = Extent3d {
-        width: RENDER_WIDTH as u32,
-        height: RENDER_HEIGHT as u32,
+        width: settings.width,
+        height: settings.height,
         ..default()
     };
 
@@ -81,16 +273,14 @@ fn spawn_camera(
     commands.spawn((
         camera,
         MainCamera,
+        CameraRig::default(),
         VisibilityBundle::default(),
         UiCameraConfig { show_ui: false },
     ));
 
-    let quad_handle = meshes.add(Mesh::from(shape::Quad::new(Vec2::new(16.0, 9.0))));
+    let quad_handle = meshes.add(Mesh::from(shape::Quad::new(Vec2::ONE)));
 
-    let material_handle = materials.add(ColorMaterial {
-        texture: Some(image_handle.clone()),
-        ..default()
-    });
+    let material_handle = materials.add(PostProcessMaterial::new(image_handle.clone()));
 
     let post_processing_pass_layer = RenderLayers::layer((RenderLayers::TOTAL_LAYERS - 1) as u8);
 
@@ -105,6 +295,7 @@ fn spawn_camera(
             ..default()
         },
         post_processing_pass_layer,
+        BaseRender,
         Name::new("Base Render"),
     ));
 
@@ -112,10 +303,8 @@ fn spawn_camera(
 
     let mut camera = Camera2dBundle::default();
     camera.camera.order = 999;
-    camera.projection.scaling_mode = ScalingMode::AutoMin {
-        min_width: 16.0,
-        min_height: 9.0,
-    };
+    camera.camera_2d.clear_color = ClearColorConfig::Custom(Color::BLACK);
+    camera.projection.scaling_mode = ScalingMode::WindowSize(1.0);
 
     commands.spawn((
         camera,
@@ -124,3 +313,105 @@ fn spawn_camera(
         UiCameraConfig { show_ui: true },
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEAD_ZONE: Vec2 = Vec2::new(1.0, 0.75);
+
+    #[test]
+    fn targets_inside_the_dead_zone_leave_the_focus_alone() {
+        let focus = Vec2::new(3.0, -2.0);
+        for offset in [
+            Vec2::new(0.99, 0.0),
+            Vec2::new(-0.99, 0.74),
+            Vec2::new(0.5, -0.74),
+        ] {
+            assert_eq!(follow_dead_zone(focus, focus + offset, DEAD_ZONE), focus);
+        }
+    }
+
+    #[test]
+    fn targets_outside_the_dead_zone_pull_the_focus_to_its_edge() {
+        let focus = Vec2::ZERO;
+        let desired = follow_dead_zone(focus, Vec2::new(1.5, 0.0), DEAD_ZONE);
+        assert!((desired - Vec2::new(0.5, 0.0)).length() < 1e-5);
+        let desired = follow_dead_zone(focus, Vec2::new(-0.5, -1.0), DEAD_ZONE);
+        assert!((desired - Vec2::new(0.0, -0.25)).length() < 1e-5);
+    }
+
+    #[test]
+    fn camera_stops_at_every_arena_edge() {
+        let bounds = Rect::new(-20.0, -10.0, 20.0, 10.0);
+        let half_view = Vec2::new(8.0, 4.0);
+        let clamp = |position| clamp_to_bounds(bounds, half_view, position);
+
+        assert_eq!(clamp(Vec2::new(0.0, 0.0)), Vec2::new(0.0, 0.0));
+        assert_eq!(clamp(Vec2::new(-30.0, 0.0)), Vec2::new(-12.0, 0.0));
+        assert_eq!(clamp(Vec2::new(30.0, 0.0)), Vec2::new(12.0, 0.0));
+        assert_eq!(clamp(Vec2::new(0.0, -30.0)), Vec2::new(0.0, -6.0));
+        assert_eq!(clamp(Vec2::new(0.0, 30.0)), Vec2::new(0.0, 6.0));
+    }
+
+    const RENDER: Vec2 = Vec2::new(960.0, 540.0);
+
+    #[test]
+    fn pixel_perfect_scales_by_whole_multiples() {
+        let scaling = RenderScaling::PixelPerfect;
+        assert_eq!(scaling.display_size(RENDER, RENDER, 1.0), RENDER);
+        assert_eq!(
+            scaling.display_size(RENDER, RENDER * 2.0, 1.0),
+            RENDER * 2.0
+        );
+        // 2560x1440 fits 2.67 times, so the image stays at 2x and the rest is letterbox.
+        assert_eq!(
+            scaling.display_size(RENDER, Vec2::new(2560.0, 1440.0), 1.0),
+            RENDER * 2.0
+        );
+        // A tall window is limited by its width.
+        assert_eq!(
+            scaling.display_size(RENDER, Vec2::new(2000.0, 3000.0), 1.0),
+            RENDER * 2.0
+        );
+        // On a 2x display the multiple is counted in physical pixels.
+        assert_eq!(
+            scaling.display_size(RENDER, Vec2::new(1440.0, 810.0), 2.0),
+            RENDER * 1.5
+        );
+    }
+
+    #[test]
+    fn pixel_perfect_never_scales_below_one() {
+        let scaling = RenderScaling::PixelPerfect;
+        assert_eq!(
+            scaling.display_size(RENDER, Vec2::new(640.0, 360.0), 1.0),
+            RENDER
+        );
+    }
+
+    #[test]
+    fn fit_letterboxes_and_stretch_fills() {
+        let window = Vec2::new(1000.0, 1000.0);
+        assert!(RenderScaling::Fit
+            .display_size(RENDER, window, 1.0)
+            .abs_diff_eq(Vec2::new(1000.0, 562.5), 1e-3));
+        assert!(RenderScaling::Fit
+            .display_size(RENDER, Vec2::new(480.0, 270.0), 1.0)
+            .abs_diff_eq(RENDER * 0.5, 1e-3));
+        assert_eq!(
+            RenderScaling::Stretch.display_size(RENDER, window, 1.0),
+            window
+        );
+    }
+
+    #[test]
+    fn camera_centers_on_arenas_smaller_than_the_view() {
+        let bounds = Rect::new(-4.0, 0.0, 6.0, 40.0);
+        let half_view = Vec2::new(8.0, 4.0);
+        assert_eq!(
+            clamp_to_bounds(bounds, half_view, Vec2::new(-3.0, 50.0)),
+            Vec2::new(1.0, 36.0)
+        );
+    }
+}
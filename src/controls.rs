@@ -0,0 +1,367 @@
+use crate::{
+    menu::{menu_button, menu_text},
+    prelude::*,
+    settings::{load_config, save_config},
+};
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputBindings::load())
+            .insert_resource(ControlsShown(false))
+            .add_startup_system(spawn_controls_ui)
+            .add_systems((capture_binding, controls_menu_actions).chain())
+            .add_systems((show_controls_ui, sync_controls_text, save_bindings));
+    }
+}
+
+const BINDINGS_FILE: &str = "bindings.ron";
+
+impl Action {
+    const ALL: [Action; 9] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Attack,
+        Action::Dash,
+        Action::Pause,
+        Action::Confirm,
+        Action::Cancel,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "controls.move_up",
+            Action::MoveDown => "controls.move_down",
+            Action::MoveLeft => "controls.move_left",
+            Action::MoveRight => "controls.move_right",
+            Action::Attack => "controls.attack",
+            Action::Dash => "controls.dash",
+            Action::Pause => "controls.pause",
+            Action::Confirm => "controls.confirm",
+            Action::Cancel => "controls.cancel",
+        }
+    }
+
+    fn in_gameplay(&self) -> bool {
+        !matches!(self, Action::Confirm | Action::Cancel)
+    }
+
+    fn in_menus(&self) -> bool {
+        !matches!(self, Action::Attack | Action::Dash)
+    }
+
+    // Two actions conflict when they can be read in the same context.
+    fn conflicts_with(&self, other: Action) -> bool {
+        *self != other
+            && ((self.in_gameplay() && other.in_gameplay())
+                || (self.in_menus() && other.in_menus()))
+    }
+}
+
+impl InputBinding {
+    fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{key:?}"),
+            InputBinding::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+
+    fn same_device(&self, other: &InputBinding) -> bool {
+        matches!(
+            (self, other),
+            (InputBinding::Key(_), InputBinding::Key(_))
+                | (InputBinding::Gamepad(_), InputBinding::Gamepad(_))
+        )
+    }
+}
+
+impl InputBindings {
+    pub fn load() -> InputBindings {
+        let mut bindings = load_config::<InputBindings>(BINDINGS_FILE);
+        for (action, defaults) in InputBindings::default().0 {
+            bindings.0.entry(action).or_insert(defaults);
+        }
+        bindings
+    }
+
+    pub fn save(&self) {
+        save_config(BINDINGS_FILE, self);
+    }
+
+    /// Makes `binding` the primary binding of its device for `action`, removing it from
+    /// any conflicting action. Returns the actions it was taken from.
+    pub fn bind(&mut self, action: Action, binding: InputBinding) -> Vec<Action> {
+        let mut unbound = Vec::new();
+        for (other, bindings) in self.0.iter_mut() {
+            if action.conflicts_with(*other) && bindings.contains(&binding) {
+                bindings.retain(|existing| *existing != binding);
+                unbound.push(*other);
+            }
+        }
+
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|existing| *existing != binding);
+        match bindings
+            .iter()
+            .position(|existing| existing.same_device(&binding))
+        {
+            Some(index) => bindings[index] = binding,
+            None => bindings.insert(0, binding),
+        }
+        unbound
+    }
+
+    fn label(&self, action: Action) -> String {
+        match self.0.get(&action) {
+            Some(bindings) if !bindings.is_empty() => bindings
+                .iter()
+                .map(InputBinding::label)
+                .collect::<Vec<_>>()
+                .join(" / "),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+fn capture_binding(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut rebind: ResMut<RebindState>,
+    mut bindings: ResMut<InputBindings>,
+    localizer: Localizer,
+) {
+    let Some(action) = rebind.listening else {
+        return;
+    };
+    let pressed = keyboard_input
+        .get_just_pressed()
+        .next()
+        .map(|key| InputBinding::Key(*key))
+        .or_else(|| {
+            gamepad_input
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Gamepad(button.button_type))
+        });
+    let Some(binding) = pressed else {
+        return;
+    };
+
+    let unbound = bindings.bind(action, binding);
+    rebind.listening = None;
+    rebind.message = unbound.first().map(|other| {
+        localizer.format(
+            "controls.conflict",
+            &[
+                ("binding", &binding.label()),
+                ("action", &localizer.text(other.key())),
+            ],
+        )
+    });
+}
+
+fn controls_menu_actions(
+    mut activated: EventReader<MenuActivated>,
+    mut shown: ResMut<ControlsShown>,
+    mut rebind: ResMut<RebindState>,
+    mut bindings: ResMut<InputBindings>,
+    localizer: Localizer,
+) {
+    for MenuActivated(action) in activated.iter() {
+        match action {
+            MenuAction::OpenControls => shown.0 = true,
+            MenuAction::CloseControls => shown.0 = false,
+            MenuAction::Rebind(action) => {
+                rebind.listening = Some(*action);
+                rebind.message = Some(localizer.format(
+                    "controls.listening",
+                    &[("action", &localizer.text(action.key()))],
+                ));
+            }
+            MenuAction::ResetBindings => {
+                *bindings = InputBindings::default();
+                rebind.message = Some(localizer.text("controls.reset_done"));
+            }
+            _ => {}
+        }
+    }
+
+    if !shown.0 && (rebind.listening.is_some() || rebind.message.is_some()) {
+        *rebind = RebindState::default();
+    }
+}
+
+fn show_controls_ui(mut ui: Query<&mut Visibility, With<ControlsUI>>, shown: Res<ControlsShown>) {
+    for mut visible in &mut ui {
+        if shown.0 {
+            *visible = Visibility::Visible;
+        } else {
+            *visible = Visibility::Hidden;
+        }
+    }
+}
+
+fn row_label(
+    action: Action,
+    bindings: &InputBindings,
+    localizer: &Localizer,
+    listening: bool,
+) -> String {
+    let value = if listening {
+        "...".to_string()
+    } else {
+        bindings.label(action)
+    };
+    format!("{}: {value}", localizer.text(action.key()))
+}
+
+fn sync_controls_text(
+    rows: Query<(&RebindRow, &Children)>,
+    mut text: Query<&mut Text, Without<RebindMessageText>>,
+    mut message: Query<&mut Text, With<RebindMessageText>>,
+    bindings: Res<InputBindings>,
+    rebind: Res<RebindState>,
+    localizer: Localizer,
+) {
+    if !bindings.is_changed() && !rebind.is_changed() && !localizer.is_changed() {
+        return;
+    }
+    for (RebindRow(action), children) in &rows {
+        for child in children {
+            if let Ok(mut text) = text.get_mut(*child) {
+                let listening = rebind.listening == Some(*action);
+                let label = row_label(*action, &bindings, &localizer, listening);
+                text.sections[0].style.font = localizer.font(&label);
+                text.sections[0].value = label;
+            }
+        }
+    }
+    for mut text in &mut message {
+        let value = rebind.message.clone().unwrap_or_default();
+        text.sections[0].style.font = localizer.font(&value);
+        text.sections[0].value = value;
+    }
+}
+
+fn save_bindings(bindings: Res<InputBindings>) {
+    if bindings.is_changed() && !bindings.is_added() {
+        bindings.save();
+    }
+}
+
+fn spawn_controls_ui(
+    mut commands: Commands,
+    localizer: Localizer,
+    bindings: Res<InputBindings>,
+    theme: Res<MenuTheme>,
+) {
+    let controls_parent = (
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(200),
+            ..default()
+        },
+        ControlsUI,
+        Name::new("Controls UI"),
+    );
+
+    let controls_box = NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(70.0), Val::Percent(95.0)),
+            position_type: PositionType::Relative,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceAround,
+            ..default()
+        },
+        background_color: Color::DARK_GRAY.into(),
+        ..default()
+    };
+
+    let message_text = (
+        TextBundle::from_section("", localizer.style("", 24.0, Color::rgb(1.0, 0.8, 0.3))),
+        RebindMessageText,
+    );
+
+    let button_size = Size::new(Val::Percent(85.0), Val::Percent(6.0));
+
+    commands.spawn(controls_parent).with_children(|commands| {
+        commands.spawn(controls_box).with_children(|commands| {
+            commands.spawn(menu_text(&theme, &localizer, "controls.title", 48.0));
+            for action in Action::ALL {
+                commands
+                    .spawn((
+                        menu_button(
+                            &theme,
+                            MenuAction::Rebind(action),
+                            CONTROLS_LAYER,
+                            button_size,
+                        ),
+                        RebindRow(action),
+                    ))
+                    .with_children(|commands| {
+                        let label = row_label(action, &bindings, &localizer, false);
+                        let style = localizer.style(&label, 26.0, theme.text);
+                        commands.spawn(TextBundle::from_section(label, style));
+                    });
+            }
+            commands.spawn(message_text);
+            for (action, text) in [
+                (MenuAction::ResetBindings, "controls.reset"),
+                (MenuAction::CloseControls, "settings.back"),
+            ] {
+                commands
+                    .spawn(menu_button(&theme, action, CONTROLS_LAYER, button_size))
+                    .with_children(|commands| {
+                        commands.spawn(menu_text(&theme, &localizer, text, 30.0));
+                    });
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding_takes_key_from_conflicting_action() {
+        let mut bindings = InputBindings::default();
+        let unbound = bindings.bind(Action::Dash, InputBinding::Key(KeyCode::J));
+        assert_eq!(unbound, vec![Action::Attack]);
+        assert_eq!(bindings.0[&Action::Dash][0], InputBinding::Key(KeyCode::J));
+        assert!(!bindings.0[&Action::Attack].contains(&InputBinding::Key(KeyCode::J)));
+    }
+
+    #[test]
+    fn gameplay_and_menu_actions_can_share_keys() {
+        let mut bindings = InputBindings::default();
+        let unbound = bindings.bind(Action::Attack, InputBinding::Key(KeyCode::Return));
+        assert!(unbound.is_empty());
+        assert!(bindings.0[&Action::Confirm].contains(&InputBinding::Key(KeyCode::Return)));
+    }
+
+    #[test]
+    fn rebinding_replaces_primary_binding_of_the_same_device() {
+        let mut bindings = InputBindings::default();
+        bindings.bind(Action::MoveUp, InputBinding::Key(KeyCode::I));
+        assert_eq!(
+            bindings.0[&Action::MoveUp],
+            vec![
+                InputBinding::Key(KeyCode::I),
+                InputBinding::Key(KeyCode::Up),
+                InputBinding::Gamepad(GamepadButtonType::DPadUp),
+            ]
+        );
+    }
+}
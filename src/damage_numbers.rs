@@ -0,0 +1,184 @@
+use crate::prelude::*;
+use bevy_easings::{custom_ease_system, CustomComponentEase, Ease, EaseFunction, EasingType, Lerp};
+use std::time::Duration;
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberPool>()
+            .add_event::<DamageNumberEvent>()
+            .add_system(reset_damage_numbers.in_schedule(OnEnter(GameState::GamePlay)))
+            .add_systems(
+                (
+                    show_damage_numbers,
+                    custom_ease_system::<DamageNumberFade>,
+                    fade_damage_numbers,
+                    recycle_damage_numbers,
+                )
+                    .chain()
+                    .in_set(OnUpdate(GameState::GamePlay)),
+            );
+    }
+}
+
+const FONT_SIZE: f32 = 48.0;
+const TEXT_SCALE: f32 = 1.0 / FONT_SIZE;
+const LIFETIME: f32 = 0.8;
+const AGGREGATE_WINDOW: f32 = 0.35;
+const RISE: f32 = 1.0;
+
+impl Lerp for DamageNumberFade {
+    type Scalar = f32;
+
+    fn lerp(&self, other: &Self, scalar: &Self::Scalar) -> Self {
+        DamageNumberFade(self.0 + (other.0 - self.0) * scalar)
+    }
+}
+
+impl DamageText {
+    fn value(&self) -> String {
+        match self {
+            DamageText::Amount(amount) => format!("{}", amount.round() as i32),
+            DamageText::Label(label) => label.clone(),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            DamageText::Amount(_) => Color::rgb(0.95, 0.2, 0.2),
+            DamageText::Label(_) => Color::rgb(1.0, 0.6, 0.2),
+        }
+    }
+}
+
+fn animate(commands: &mut Commands, entity: Entity, position: Vec3, emphasis: f32) {
+    let start = position + Vec3::new(-0.2, 1.4, 0.0);
+    let start = Transform::from_translation(start.truncate().extend(200.0))
+        .with_scale(Vec3::splat(TEXT_SCALE * 0.5 * emphasis));
+    let end = Transform::from_translation(start.translation + Vec3::Y * RISE)
+        .with_scale(Vec3::splat(TEXT_SCALE * emphasis));
+    let duration = Duration::from_secs_f32(LIFETIME);
+
+    commands.entity(entity).insert((
+        start,
+        start.ease_to(end, EaseFunction::BackOut, EasingType::Once { duration }),
+        DamageNumberFade(1.0),
+        DamageNumberFade(1.0).ease_to(
+            DamageNumberFade(0.0),
+            EaseFunction::QuadraticIn,
+            EasingType::Once { duration },
+        ),
+        Visibility::Visible,
+    ));
+}
+
+fn reset_damage_numbers(mut pool: ResMut<DamageNumberPool>) {
+    pool.free.clear();
+    pool.active.clear();
+}
+
+fn show_damage_numbers(
+    mut commands: Commands,
+    mut events: EventReader<DamageNumberEvent>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<(&mut DamageNumber, &mut Text)>,
+    settings: Res<UserSettings>,
+    assets: Res<AssetServer>,
+) {
+    if !settings.damage_numbers {
+        events.clear();
+        return;
+    }
+
+    for event in events.iter() {
+        if let (Some(target), DamageText::Amount(amount)) = (event.target, &event.text) {
+            let active = pool.active.get(&target).copied();
+            if let Some((entity, (mut number, mut text))) = active
+                .and_then(|entity| numbers.get_mut(entity).ok().map(|number| (entity, number)))
+            {
+                if number.lifetime.elapsed_secs() < AGGREGATE_WINDOW {
+                    number.amount += amount;
+                    number.lifetime.reset();
+                    text.sections[0].value = DamageText::Amount(number.amount).value();
+                    let emphasis = (1.0 + number.amount / 50.0).min(2.0);
+                    animate(&mut commands, entity, event.position, emphasis);
+                    continue;
+                }
+            }
+        }
+
+        let amount = match event.text {
+            DamageText::Amount(amount) => amount,
+            DamageText::Label(_) => 0.0,
+        };
+        let number = DamageNumber {
+            target: event.target,
+            amount,
+            lifetime: Timer::from_seconds(LIFETIME, TimerMode::Once),
+        };
+        let text = Text::from_section(
+            event.text.value(),
+            TextStyle {
+                font: assets.load("fonts/dos.ttf"),
+                font_size: FONT_SIZE,
+                color: event.text.color(),
+            },
+        )
+        .with_alignment(TextAlignment::Center);
+
+        let entity = match pool.free.pop().filter(|entity| numbers.contains(*entity)) {
+            Some(entity) => {
+                commands.entity(entity).insert((number, text));
+                entity
+            }
+            None => commands
+                .spawn((
+                    Text2dBundle { text, ..default() },
+                    number,
+                    GamePlayEntity,
+                    Name::new("Damage Number"),
+                ))
+                .id(),
+        };
+
+        if let Some(target) = event.target {
+            pool.active.insert(target, entity);
+        }
+        animate(&mut commands, entity, event.position, 1.0);
+    }
+}
+
+fn fade_damage_numbers(
+    mut numbers: Query<(&DamageNumberFade, &mut Text), Changed<DamageNumberFade>>,
+) {
+    for (fade, mut text) in &mut numbers {
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(fade.0);
+        }
+    }
+}
+
+fn recycle_damage_numbers(
+    mut numbers: Query<(Entity, &mut DamageNumber, &mut Visibility)>,
+    mut pool: ResMut<DamageNumberPool>,
+    time: Res<Time>,
+) {
+    for (entity, mut number, mut visibility) in &mut numbers {
+        if number.lifetime.finished() {
+            continue;
+        }
+        number.lifetime.tick(time.delta());
+        if !number.lifetime.just_finished() {
+            continue;
+        }
+
+        *visibility = Visibility::Hidden;
+        if let Some(target) = number.target {
+            if pool.active.get(&target) == Some(&entity) {
+                pool.active.remove(&target);
+            }
+        }
+        pool.free.push(entity);
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    arena::ArenaLayout,
+    attacks::{blade_attack, dagger_hits},
+    enemy::{enemy_death_check, spawn_enemy_entity},
+    loot::spawn_loot,
+    prelude::*,
+};
+
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerDamaged>().add_systems(
+            (
+                elite_tint,
+                elite_spawn_shake,
+                elite_vampiric,
+                elite_death_effects
+                    .after(blade_attack)
+                    .after(dagger_hits)
+                    .before(enemy_death_check),
+            )
+                .in_set(OnUpdate(GameState::GamePlay)),
+        );
+    }
+}
+
+impl Affix {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Affix::Fast => "Fast",
+            Affix::Armored => "Armored",
+            Affix::Vampiric => "Vampiric",
+            Affix::Explosive => "Explosive",
+            Affix::Splitting => "Splitting",
+            Affix::Shielded => "Shielded",
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            Affix::Fast => Color::rgb(1.0, 1.0, 0.4),
+            Affix::Armored => Color::rgb(0.6, 0.6, 0.7),
+            Affix::Vampiric => Color::rgb(1.0, 0.3, 0.3),
+            Affix::Explosive => Color::rgb(1.0, 0.6, 0.2),
+            Affix::Splitting => Color::rgb(0.4, 1.0, 0.4),
+            Affix::Shielded => Color::rgb(0.4, 0.7, 1.0),
+        }
+    }
+}
+
+impl Elite {
+    pub fn has(&self, affix: Affix) -> bool {
+        self.affixes.contains(&affix)
+    }
+
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let mut damage = damage;
+        if self.has(Affix::Armored) {
+            damage *= 0.5;
+        }
+        let absorbed = damage.min(self.shield);
+        self.shield -= absorbed;
+        damage - absorbed
+    }
+}
+
+pub fn roll_elite(
+    global_rng: &mut GlobalRng,
+    wave: &Respawn,
+    wave_buf: usize,
+    enemy: &mut Enemy,
+) -> Option<Elite> {
+    if wave.elite_affixes.is_empty() {
+        return None;
+    }
+
+    let chance = (wave.elite_chance + 0.05 * wave_buf as f32).min(1.0);
+    if !global_rng.chance(chance as f64) {
+        return None;
+    }
+
+    let mut pool = wave.elite_affixes.clone();
+    global_rng.shuffle(&mut pool);
+    let max_affixes = (1 + wave_buf).min(3).min(pool.len());
+    pool.truncate(global_rng.usize(1..=max_affixes));
+
+    let mut elite = Elite {
+        affixes: pool,
+        shield: 0.0,
+    };
+
+    enemy.health *= 1.5;
+    enemy.max_health *= 1.5;
+    enemy.damage *= 1.25;
+    if elite.has(Affix::Fast) {
+        enemy.speed *= 1.5;
+    }
+    if elite.has(Affix::Shielded) {
+        elite.shield = enemy.health * 0.5;
+    }
+    Some(elite)
+}
+
+pub fn make_elite(commands: &mut Commands, assets: &GameAssets, entity: Entity, elite: Elite) {
+    let font = assets.load("fonts/dos.ttf");
+
+    let name = elite
+        .affixes
+        .iter()
+        .map(|affix| affix.name())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let name_tag = (
+        Text2dBundle {
+            text: Text::from_section(
+                name,
+                TextStyle {
+                    font,
+                    font_size: 32.0,
+                    color: elite.affixes[0].tint(),
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            transform: Transform::from_xyz(0.0, 1.8, 1.0)
+                .with_scale(Vec3::splat(PIXEL_TO_WORLD * 0.5)),
+            ..default()
+        },
+        EliteNameTag,
+        Name::new("Elite Name Tag"),
+    );
+
+    commands
+        .entity(entity)
+        .insert(elite)
+        .with_children(|commands| {
+            commands.spawn(name_tag);
+        });
+}
+
+pub fn elite_tint(mut elites: Query<(&mut Sprite, &Elite), Added<Elite>>) {
+    for (mut sprite, elite) in &mut elites {
+        sprite.color = elite.affixes[0].tint();
+    }
+}
+
+fn elite_spawn_shake(elites: Query<&Elite, Added<Elite>>, mut shake: EventWriter<ScreenShake>) {
+    for elite in &elites {
+        shake.send(ScreenShake(0.15 * elite.affixes.len() as f32));
+    }
+}
+
+fn elite_vampiric(
+    mut enemies: Query<(&Collider, &GlobalTransform, &mut Enemy, &Elite)>,
+    player: Query<Entity, (With<Player>, Without<Invulnerable>)>,
+    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    for (collider, transform, mut enemy, elite) in &mut enemies {
+        if !elite.has(Affix::Vampiric) {
+            continue;
+        }
+        rapier_context.intersections_with_shape(
+            transform.translation().truncate(),
+            0.0,
+            collider,
+            QueryFilter::new(),
+            |entity| {
+                if entity == player {
+                    enemy.health =
+                        (enemy.health + enemy.damage * time.delta_seconds()).min(enemy.max_health);
+                }
+                true
+            },
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn elite_death_effects(
+    mut commands: Commands,
+    mut shake: EventWriter<ScreenShake>,
+    mut damage_numbers: EventWriter<DamageNumberEvent>,
+    enemies: Query<(&Transform, &Enemy, &Elite)>,
+    mut player: Query<(&Transform, &mut Player), (Without<Enemy>, Without<Invulnerable>)>,
+    potion_manager: Res<PotionManager>,
+    mut global_rng: ResMut<GlobalRng>,
+    assets: GameAssets,
+    mut sfx: EventWriter<PlaySfx>,
+    mut damaged: EventWriter<PlayerDamaged>,
+    arena: ArenaLayout,
+) {
+    let arena = arena.get();
+
+    for (transform, enemy, elite) in &enemies {
+        if enemy.health > 0.0 {
+            continue;
+        }
+
+        let position = transform.translation;
+
+        if elite.has(Affix::Explosive) {
+            if let Ok((player_transform, mut player)) = player.get_single_mut() {
+                if position
+                    .truncate()
+                    .distance(player_transform.translation.truncate())
+                    < 2.5
+                {
+                    let damage = enemy.damage * 2.0;
+                    player.health -= damage;
+                    damaged.send(PlayerDamaged { amount: damage });
+                }
+            }
+            damage_numbers.send(DamageNumberEvent {
+                target: None,
+                position,
+                text: DamageText::Label("BOOM".to_string()),
+            });
+            shake.send(ScreenShake(0.6));
+            sfx.send(PlaySfx::at(Sfx::Explosion, position));
+        }
+
+        if elite.has(Affix::Splitting) {
+            for offset in [Vec3::new(-0.8, 0.0, 0.0), Vec3::new(0.8, 0.0, 0.0)] {
+                let mut child = enemy.clone();
+                child.max_health = enemy.max_health * 0.25;
+                child.health = child.max_health;
+                child.damage *= 0.5;
+                let mut translation = position + offset;
+                if let Some(arena) = arena {
+                    if !arena.is_walkable(arena.world_to_tile(translation.truncate())) {
+                        translation = arena
+                            .nearest_floor(translation.truncate())
+                            .extend(translation.z);
+                    }
+                }
+                spawn_enemy_entity(&mut commands, &assets, &mut global_rng, child, translation);
+            }
+        }
+
+        let drop_table = DropTable::elite();
+        for _i in 0..elite.affixes.len() {
+            let Some(loot) = drop_table.roll(&mut *global_rng) else {
+                continue;
+            };
+            let offset = Vec3::new(
+                global_rng.f32_normalized(),
+                global_rng.f32_normalized(),
+                0.0,
+            );
+            spawn_loot(
+                &mut commands,
+                &assets,
+                &mut global_rng,
+                &potion_manager,
+                loot,
+                position + offset,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wave(elite_chance: f32, elite_affixes: Vec<Affix>) -> Respawn {
+        Respawn {
+            next_spawn: Timer::from_seconds(1.0, TimerMode::Repeating),
+            respawn_size: 1,
+            to_spawn: enemy(),
+            elite_chance,
+            elite_affixes,
+        }
+    }
+
+    fn enemy() -> Enemy {
+        Enemy {
+            speed: 2.0,
+            health: 10.0,
+            max_health: 10.0,
+            damage: 4.0,
+            asset: "sprites/Kobold.png".to_string(),
+        }
+    }
+
+    fn elites_rolled(wave: &Respawn, wave_buf: usize) -> usize {
+        let mut global_rng = GlobalRng::with_seed(7);
+        (0..200)
+            .filter_map(|_| roll_elite(&mut global_rng, wave, wave_buf, &mut enemy()))
+            .count()
+    }
+
+    #[test]
+    fn elite_chance_sets_the_threshold() {
+        let affixes = vec![Affix::Fast, Affix::Armored];
+        assert_eq!(elites_rolled(&wave(0.0, affixes.clone()), 0), 0);
+        assert_eq!(elites_rolled(&wave(1.0, affixes.clone()), 0), 200);
+        // Every loop through the waves adds 5%, so twenty loops guarantee an elite.
+        assert_eq!(elites_rolled(&wave(0.0, affixes.clone()), 20), 200);
+        assert_eq!(elites_rolled(&wave(1.0, Vec::new()), 0), 0);
+    }
+
+    #[test]
+    fn elites_are_tougher_and_carry_a_few_affixes() {
+        let wave = wave(1.0, vec![Affix::Fast, Affix::Armored, Affix::Shielded]);
+        let mut global_rng = GlobalRng::with_seed(3);
+        for wave_buf in 0..4 {
+            let mut enemy = enemy();
+            let elite = roll_elite(&mut global_rng, &wave, wave_buf, &mut enemy).unwrap();
+
+            assert!((1..=(1 + wave_buf).min(3)).contains(&elite.affixes.len()));
+            assert_eq!(enemy.health, 15.0);
+            assert_eq!(enemy.max_health, 15.0);
+            assert_eq!(enemy.damage, 5.0);
+            assert_eq!(enemy.speed, if elite.has(Affix::Fast) { 3.0 } else { 2.0 });
+            assert_eq!(
+                elite.shield,
+                if elite.has(Affix::Shielded) { 7.5 } else { 0.0 }
+            );
+        }
+    }
+
+    #[test]
+    fn absorb_stacks_armor_before_shield() {
+        let mut plain = Elite {
+            affixes: vec![Affix::Fast],
+            shield: 0.0,
+        };
+        assert_eq!(plain.absorb(8.0), 8.0);
+
+        let mut armored = Elite {
+            affixes: vec![Affix::Armored],
+            shield: 0.0,
+        };
+        assert_eq!(armored.absorb(8.0), 4.0);
+
+        let mut shielded = Elite {
+            affixes: vec![Affix::Shielded],
+            shield: 5.0,
+        };
+        assert_eq!(shielded.absorb(3.0), 0.0);
+        assert_eq!(shielded.absorb(3.0), 1.0);
+        assert_eq!(shielded.shield, 0.0);
+        assert_eq!(shielded.absorb(3.0), 3.0);
+
+        let mut both = Elite {
+            affixes: vec![Affix::Armored, Affix::Shielded],
+            shield: 5.0,
+        };
+        assert_eq!(both.absorb(30.0), 10.0);
+        assert_eq!(both.shield, 0.0);
+    }
+}
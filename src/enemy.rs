@@ -1,20 +1,26 @@
+use crate::arena::ArenaLayout;
+use crate::elite::{make_elite, roll_elite};
 use crate::prelude::*;
-use crate::ui::spawn_world_text;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            (
-                enemy_death_check,
-                enemy_movement,
-                spawn_enemy,
-                despawn_enemy,
-                enemy_damage_player.after(enemy_movement),
+        app.add_event::<EnemyKilled>()
+            .add_event::<WaveStarted>()
+            .add_event::<PlayerDamaged>()
+            .add_event::<SurvivalTimeChanged>()
+            .add_systems(
+                (
+                    enemy_death_check,
+                    enemy_movement,
+                    spawn_enemy,
+                    despawn_enemy,
+                    enemy_damage_player.after(enemy_movement),
+                )
+                    .in_set(OnUpdate(GameState::GamePlay)),
             )
-                .in_set(OnUpdate(GameState::GamePlay)),
-        );
+            .add_system(reset_spawn_manager.in_schedule(OnEnter(GameState::GamePlay)));
     }
 }
 
@@ -29,29 +35,47 @@ fn despawn_enemy(
         if Vec2::distance(
             player.translation.truncate(),
             transform.translation.truncate(),
-        ) > 30.0
+        ) > ENEMY_DESPAWN_DISTANCE
         {
             commands.entity(enemy).despawn_recursive();
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_enemy(
     mut commands: Commands,
     mut spawn_manager: ResMut<SpawnManager>,
     player: Query<&Transform, With<Player>>,
-    assets: Res<AssetServer>,
+    assets: GameAssets,
     mut global_rng: ResMut<GlobalRng>,
+    mut wave_started: EventWriter<WaveStarted>,
+    mut survival_time: EventWriter<SurvivalTimeChanged>,
     time: Res<Time>,
+    arena: ArenaLayout,
 ) {
     let player_transform = player.single();
+    let arena = arena.get();
 
+    let previous_elapsed = spawn_manager.global_time.elapsed_secs();
     spawn_manager.global_time.tick(time.delta());
 
+    let seconds = spawn_manager.global_time.elapsed().as_secs();
+    if previous_elapsed == 0.0 || previous_elapsed as u64 != seconds {
+        survival_time.send(SurvivalTimeChanged { seconds });
+    }
+
     let current_wave = (spawn_manager.global_time.elapsed_secs() / 20.0) as usize;
     let wave_index = current_wave % spawn_manager.waves.len();
     let wave_buf = current_wave / spawn_manager.waves.len();
 
+    if previous_elapsed == 0.0 || (previous_elapsed / 20.0) as usize != current_wave {
+        wave_started.send(WaveStarted {
+            wave: wave_index,
+            loop_index: wave_buf,
+        });
+    }
+
     let wave = &mut spawn_manager.waves[wave_index];
     let size = (wave.respawn_size as f32 * 1.3_f32.powf(wave_buf as f32)) as i32;
 
@@ -72,74 +96,111 @@ fn spawn_enemy(
             let mut enemy = wave.to_spawn.clone();
             enemy.speed *= 1.3_f32.powf(wave_buf as f32);
             enemy.health *= 1.3_f32.powf(wave_buf as f32);
+            enemy.max_health *= 1.3_f32.powf(wave_buf as f32);
 
             target_translation += player_transform.translation.truncate().extend(0.0);
-            commands.spawn((
-                SpriteBundle {
-                    texture: assets.load(&wave.to_spawn.asset),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(69.0 * PIXEL_TO_WORLD, 80.0 * PIXEL_TO_WORLD)),
-                        ..default()
-                    },
-                    transform: Transform::from_translation(target_translation),
-                    ..default()
-                },
+            if let Some(arena) = arena {
+                target_translation = arena
+                    .spawn_position(
+                        &mut global_rng,
+                        target_translation.truncate(),
+                        player_transform.translation.truncate(),
+                    )
+                    .extend(target_translation.z);
+            }
+
+            let elite = roll_elite(&mut global_rng, wave, wave_buf, &mut enemy);
+            let entity = spawn_enemy_entity(
+                &mut commands,
+                &assets,
+                &mut global_rng,
                 enemy,
-                Name::new("Enemy"),
-                RngComponent::from(&mut global_rng),
-                RigidBody::Dynamic,
-                LockedAxes::ROTATION_LOCKED_Z,
-                Damping {
-                    linear_damping: 100.0,
-                    angular_damping: 1.0,
-                },
-                GamePlayEntity,
-                Collider::capsule(Vec2::new(0.0, 0.55), Vec2::new(0.0, -0.55), 0.8),
-            ));
+                target_translation,
+            );
+
+            if let Some(elite) = elite {
+                make_elite(&mut commands, &assets, entity, elite);
+            }
         }
     }
 }
 
-fn enemy_movement(
+pub fn spawn_enemy_entity(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    global_rng: &mut GlobalRng,
+    enemy: Enemy,
+    translation: Vec3,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: assets.load(&enemy.asset),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(69.0 * PIXEL_TO_WORLD, 80.0 * PIXEL_TO_WORLD)),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            enemy,
+            Name::new("Enemy"),
+            RngComponent::from(global_rng),
+            RigidBody::Dynamic,
+            LockedAxes::ROTATION_LOCKED_Z,
+            Damping {
+                linear_damping: 100.0,
+                angular_damping: 1.0,
+            },
+            GamePlayEntity,
+            Collider::capsule(Vec2::new(0.0, 0.55), Vec2::new(0.0, -0.55), 0.8),
+        ))
+        .id()
+}
+
+pub fn enemy_movement(
     player: Query<&Transform, (With<Player>, Without<Enemy>)>,
     mut enemy: Query<(&mut Transform, &mut Sprite, &Enemy)>,
+    flow_field: Res<FlowField>,
+    arena: ArenaLayout,
     time: Res<Time>,
 ) {
     let player_transform = player.single();
+    let arena = arena.get();
 
     for (mut transform, mut sprite, enemy) in &mut enemy {
-        let direction = (transform.translation.truncate()
-            - player_transform.translation.truncate())
-        .normalize();
-        sprite.flip_x = direction.x > 0.0;
-        transform.translation -= (direction * time.delta_seconds() * enemy.speed).extend(0.);
+        let position = transform.translation.truncate();
+        let direction = arena
+            .and_then(|arena| flow_field.direction(arena, position))
+            .unwrap_or_else(|| (player_transform.translation.truncate() - position).normalize());
+        sprite.flip_x = direction.x < 0.0;
+        transform.translation += (direction * time.delta_seconds() * enemy.speed).extend(0.);
     }
 }
 
 fn damage_player(
-    commands: &mut Commands,
-    assets: &AssetServer,
+    damage_numbers: &mut EventWriter<DamageNumberEvent>,
     enemy: &mut Enemy,
     position: &Transform,
     damage: f32,
 ) {
-    spawn_world_text(
-        commands,
-        assets,
-        position.translation.truncate(),
-        &format!("{:?}", damage as i32),
-    );
+    damage_numbers.send(DamageNumberEvent {
+        target: None,
+        position: position.translation,
+        text: DamageText::Amount(damage),
+    });
 
     enemy.health -= damage;
 }
 
 fn enemy_damage_player(
     enemies: Query<(&Collider, &GlobalTransform, &Enemy)>,
-    mut player: Query<&mut Player>,
+    mut player: Query<&mut Player, Without<Invulnerable>>,
+    mut shake: EventWriter<ScreenShake>,
     rapier_context: Res<RapierContext>,
     time: Res<Time>,
-    _assets: Res<AssetServer>,
-    _audio: Res<Audio>,
+    mut sfx: EventWriter<PlaySfx>,
+    mut damaged: EventWriter<PlayerDamaged>,
 ) {
     for (collider, transform, enemy) in &enemies {
         rapier_context.intersections_with_shape(
@@ -149,7 +210,11 @@ fn enemy_damage_player(
             QueryFilter::new(),
             |entity| {
                 if let Ok(mut player) = player.get_mut(entity) {
-                    player.health -= enemy.damage * time.delta_seconds();
+                    let damage = enemy.damage * time.delta_seconds();
+                    player.health -= damage;
+                    damaged.send(PlayerDamaged { amount: damage });
+                    shake.send(ScreenShake(damage * 0.1));
+                    sfx.send(PlaySfx::at(Sfx::EnemyAttack, transform.translation()));
                 }
                 true
             },
@@ -157,10 +222,25 @@ fn enemy_damage_player(
     }
 }
 
-fn enemy_death_check(mut commands: Commands, mut enemies: Query<(Entity, &Transform, &Enemy)>) {
-    for (entity, _transform, enemy) in &mut enemies {
+pub fn enemy_death_check(
+    mut commands: Commands,
+    mut enemies: Query<(Entity, &Transform, &Enemy)>,
+    mut killed: EventWriter<EnemyKilled>,
+) {
+    for (entity, transform, enemy) in &mut enemies {
         if enemy.health <= 0.0 {
+            killed.send(EnemyKilled {
+                position: transform.translation,
+                asset: enemy.asset.clone(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
 }
+
+fn reset_spawn_manager(mut spawn_manager: ResMut<SpawnManager>) {
+    spawn_manager.global_time.reset();
+    for wave in spawn_manager.waves.iter_mut() {
+        wave.next_spawn.reset();
+    }
+}
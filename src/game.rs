@@ -0,0 +1,288 @@
+use crate::prelude::*;
+use bevy::{
+    app::PluginGroupBuilder,
+    asset::{Asset, AssetPath, AssetPlugin},
+    audio::AudioPlugin,
+    ecs::system::SystemParam,
+    hierarchy::HierarchyPlugin,
+    transform::TransformPlugin,
+};
+use bevy_easings::EasingsPlugin;
+use bevy_embedded_assets::EmbeddedAssetPlugin;
+
+/// The whole game on top of `DefaultPlugins`, or `MinimalPlugins` when headless, for
+/// running it standalone.
+pub struct BevyQuestPlugins(pub BevyQuestConfig);
+
+impl PluginGroup for BevyQuestPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        if self.0.headless {
+            return MinimalPlugins
+                .build()
+                .add(TransformPlugin)
+                .add(HierarchyPlugin)
+                .add(AssetPlugin::default())
+                .add(BevyQuestPlugin::new(self.0));
+        }
+
+        let mut group = DefaultPlugins
+            .build()
+            .set(WindowPlugin {
+                primary_window: Some(self.0.window.clone()),
+                ..default()
+            })
+            .add_before::<AssetPlugin, _>(EmbeddedAssetPlugin);
+        if !self.0.audio {
+            group = group.disable::<AudioPlugin>();
+        }
+        group.add(BevyQuestPlugin::new(self.0))
+    }
+}
+
+/// The game itself, for apps that already add `DefaultPlugins` (or `MinimalPlugins` and
+/// `AssetPlugin` when headless).
+#[derive(Default)]
+pub struct BevyQuestPlugin {
+    config: BevyQuestConfig,
+}
+
+impl BevyQuestPlugin {
+    pub fn new(config: BevyQuestConfig) -> BevyQuestPlugin {
+        BevyQuestPlugin { config }
+    }
+}
+
+impl Plugin for BevyQuestPlugin {
+    fn build(&self, app: &mut App) {
+        let content = match &self.config.content {
+            ContentSource::BuiltIn => GameContent::built_in(),
+            ContentSource::Custom(content) => content.clone(),
+        };
+        let rng = match self.config.seed {
+            Some(seed) => RngPlugin::new().with_rng_seed(seed),
+            None => RngPlugin::default(),
+        };
+
+        app.insert_resource(SpawnManager {
+            global_time: Stopwatch::new(),
+            waves: content.waves,
+        })
+        .insert_resource(PotionManager {
+            potion_time: Stopwatch::new(),
+            potion_waves: content.potions,
+        })
+        .add_plugin(rng)
+        .add_state::<GameState>()
+        .add_plugin(PlayerPlugin)
+        .add_plugin(EnemyPlugin)
+        .add_plugin(ElitePlugin)
+        .add_plugin(PotionsPlugin)
+        .add_plugin(PropsPlugin)
+        .add_plugin(LootPlugin)
+        .add_plugin(AttackPlugin)
+        .add_plugin(TargetingPlugin)
+        .add_plugin(PathfindingPlugin)
+        .add_plugin(ArenaPlugin)
+        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(50.0))
+        .insert_resource(RapierConfiguration {
+            gravity: Vec2::ZERO,
+            ..default()
+        })
+        .add_system(despawn_game_play.in_schedule(OnEnter(GameState::GameOver)));
+
+        if self.config.headless {
+            add_headless(app);
+        } else {
+            add_presentation(app, &self.config);
+        }
+    }
+}
+
+fn add_presentation(app: &mut App, config: &BevyQuestConfig) {
+    app.add_plugin(EasingsPlugin)
+        .add_plugin(HealthBarPlugin)
+        .add_plugin(GameCameraPlugin)
+        .add_plugin(PostProcessPlugin)
+        .add_plugin(GameUiPlugin)
+        .add_plugin(ActionsPlugin)
+        .add_plugin(MenuPlugin)
+        .add_plugin(HudPlugin)
+        .add_plugin(DamageNumbersPlugin)
+        .add_plugin(AimPlugin)
+        .add_plugin(AnimationPlugin)
+        .add_plugin(BackgroundPlugin)
+        .add_plugin(PausePlugin)
+        .add_plugin(SettingsPlugin)
+        .add_plugin(ControlsPlugin)
+        .add_plugin(LocalizationPlugin)
+        .init_resource::<Animations>()
+        .register_type::<TextureAtlasSprite>();
+
+    if config.audio {
+        app.add_plugin(SoundPlugin).add_plugin(MusicPlugin);
+    } else {
+        // Gameplay still sends sound events, they just go unheard.
+        app.add_event::<PlaySfx>();
+    }
+
+    #[cfg(feature = "debug_tools")]
+    if config.debug_tools {
+        app.add_plugin(EditorPlugin::default());
+        //app.add_plugin(RapierDebugRenderPlugin::default());
+    }
+}
+
+// Stands in for the events and resources the presentation plugins would provide, and
+// skips the main menu. Input comes from whoever drives `ActionState`.
+fn add_headless(app: &mut App) {
+    app.insert_resource(Headless)
+        .add_event::<DamageNumberEvent>()
+        .add_event::<ScreenShake>()
+        .add_event::<PlaySfx>()
+        .init_resource::<ActionState>()
+        .init_resource::<Aim>()
+        .init_resource::<UserSettings>()
+        .insert_resource(NextState(Some(GameState::GamePlay)));
+}
+
+/// Loads assets through the `AssetServer`, or hands out placeholder handles when headless
+/// since there are no loaders for sprites and sounds there.
+#[derive(SystemParam)]
+pub struct GameAssets<'w> {
+    server: Res<'w, AssetServer>,
+    headless: Option<Res<'w, Headless>>,
+}
+
+impl<'w> GameAssets<'w> {
+    pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
+        match self.headless {
+            Some(_) => Handle::default(),
+            None => self.server.load(path),
+        }
+    }
+}
+
+impl Default for BevyQuestConfig {
+    fn default() -> BevyQuestConfig {
+        BevyQuestConfig {
+            content: ContentSource::BuiltIn,
+            seed: None,
+            debug_tools: false,
+            audio: true,
+            window: Window::default(),
+            headless: false,
+        }
+    }
+}
+
+impl BevyQuestConfig {
+    pub fn with_content(mut self, content: GameContent) -> BevyQuestConfig {
+        self.content = ContentSource::Custom(content);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> BevyQuestConfig {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_debug_tools(mut self, enabled: bool) -> BevyQuestConfig {
+        self.debug_tools = enabled;
+        self
+    }
+
+    pub fn with_audio(mut self, enabled: bool) -> BevyQuestConfig {
+        self.audio = enabled;
+        self
+    }
+
+    pub fn with_window(mut self, window: Window) -> BevyQuestConfig {
+        self.window = window;
+        self
+    }
+
+    pub fn with_headless(mut self, enabled: bool) -> BevyQuestConfig {
+        self.headless = enabled;
+        self
+    }
+}
+
+impl GameContent {
+    pub fn built_in() -> GameContent {
+        GameContent {
+            waves: vec![
+                Respawn {
+                    next_spawn: Timer::from_seconds(1.0, TimerMode::Repeating),
+                    respawn_size: 2,
+                    to_spawn: Enemy {
+                        speed: 1.3,
+                        health: 10.0,
+                        max_health: 10.0,
+                        asset: "sprites/Kobold.png".to_string(),
+                        damage: 1.0,
+                    },
+                    elite_chance: 0.05,
+                    elite_affixes: vec![Affix::Fast, Affix::Armored, Affix::Splitting],
+                },
+                Respawn {
+                    next_spawn: Timer::from_seconds(5.0, TimerMode::Repeating),
+                    respawn_size: 2,
+                    to_spawn: Enemy {
+                        speed: 2.2,
+                        health: 5.0,
+                        max_health: 5.0,
+                        asset: "sprites/Skeleton.png".to_string(),
+                        damage: 2.0,
+                    },
+                    elite_chance: 0.05,
+                    elite_affixes: vec![Affix::Fast, Affix::Shielded, Affix::Explosive],
+                },
+                Respawn {
+                    next_spawn: Timer::from_seconds(10.0, TimerMode::Repeating),
+                    respawn_size: 2,
+                    to_spawn: Enemy {
+                        speed: 0.8,
+                        health: 30.0,
+                        max_health: 30.0,
+                        asset: "sprites/Orc.png".to_string(),
+                        damage: 10.0,
+                    },
+                    elite_chance: 0.1,
+                    elite_affixes: vec![
+                        Affix::Armored,
+                        Affix::Vampiric,
+                        Affix::Shielded,
+                        Affix::Splitting,
+                    ],
+                },
+                Respawn {
+                    next_spawn: Timer::from_seconds(15.0, TimerMode::Repeating),
+                    respawn_size: 2,
+                    to_spawn: Enemy {
+                        speed: 2.5,
+                        health: 15.0,
+                        max_health: 15.0,
+                        asset: "sprites/Naga.png".to_string(),
+                        damage: 10.0,
+                    },
+                    elite_chance: 0.1,
+                    elite_affixes: vec![Affix::Fast, Affix::Vampiric, Affix::Explosive],
+                },
+            ],
+            potions: vec![RePotion {
+                next_potion_spawn: Timer::from_seconds(5.0, TimerMode::Repeating),
+                potion_count: 1,
+                to_spawn_potion: Potion {
+                    health: 1.0,
+                    asset: "sprites/health_potion_small.png".to_string(),
+                },
+            }],
+        }
+    }
+}
+
+fn despawn_game_play(mut commands: Commands, entities: Query<Entity, With<GamePlayEntity>>) {
+    for entity in &entities {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use bevy::sprite::Anchor;
+
+pub struct HealthBarPlugin;
+
+impl Plugin for HealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                attach_health_bars,
+                update_health_bars.after(attach_health_bars),
+            )
+                .in_set(OnUpdate(GameState::GamePlay)),
+        );
+    }
+}
+
+const BAR_HEIGHT: f32 = 0.15;
+const BAR_OFFSET: f32 = 1.5;
+const BAR_VISIBLE_SECONDS: f32 = 2.0;
+const BAR_FADE_SECONDS: f32 = 0.5;
+
+fn bar_width(max_health: f32) -> f32 {
+    (0.6 + max_health.max(1.0).ln() * 0.25).clamp(0.6, 2.5)
+}
+
+fn attach_health_bars(
+    mut commands: Commands,
+    enemies: Query<(Entity, &Enemy), (Changed<Enemy>, Without<HealthBar>)>,
+    settings: Res<UserSettings>,
+) {
+    if !settings.enemy_health_bars {
+        return;
+    }
+
+    for (entity, enemy) in &enemies {
+        if enemy.health >= enemy.max_health || enemy.health <= 0.0 {
+            continue;
+        }
+
+        let width = bar_width(enemy.max_health);
+        let background = commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::BLACK,
+                    custom_size: Some(Vec2::new(width, BAR_HEIGHT)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, BAR_OFFSET, 1.0),
+                ..default()
+            })
+            .id();
+        let fill = commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::RED,
+                    custom_size: Some(Vec2::new(width, BAR_HEIGHT)),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                transform: Transform::from_xyz(-width / 2.0, BAR_OFFSET, 1.1),
+                ..default()
+            })
+            .id();
+
+        commands
+            .entity(entity)
+            .push_children(&[background, fill])
+            .insert(HealthBar {
+                background,
+                fill,
+                width,
+                last_health: enemy.max_health,
+                shown: Timer::from_seconds(BAR_VISIBLE_SECONDS + BAR_FADE_SECONDS, TimerMode::Once),
+            });
+    }
+}
+
+fn update_health_bars(
+    mut enemies: Query<(&Enemy, &mut HealthBar)>,
+    mut sprites: Query<&mut Sprite>,
+    settings: Res<UserSettings>,
+    time: Res<Time>,
+) {
+    for (enemy, mut bar) in &mut enemies {
+        if enemy.health != bar.last_health {
+            bar.last_health = enemy.health;
+            bar.shown.reset();
+        }
+        bar.shown.tick(time.delta());
+
+        let alpha = if settings.enemy_health_bars {
+            (bar.shown.remaining_secs() / BAR_FADE_SECONDS).min(1.0)
+        } else {
+            0.0
+        };
+        let fraction = (enemy.health / enemy.max_health).clamp(0.0, 1.0);
+
+        if let Ok(mut background) = sprites.get_mut(bar.background) {
+            background.color.set_a(alpha * 0.8);
+        }
+        if let Ok(mut fill) = sprites.get_mut(bar.fill) {
+            fill.color.set_a(alpha);
+            fill.custom_size = Some(Vec2::new(bar.width * fraction, BAR_HEIGHT));
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::prelude::*;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyCount>()
+            .add_system(spawn_hud.in_schedule(OnEnter(GameState::GamePlay)))
+            .add_systems(
+                (hud_timer, hud_kills, hud_wave, hud_progress, hud_dash)
+                    .in_set(OnUpdate(GameState::GamePlay)),
+            );
+    }
+}
+
+const XP_PER_LEVEL: u32 = 10;
+
+pub fn level_progress(xp: u32) -> (u32, u32, u32) {
+    let mut level = 1;
+    let mut remaining = xp;
+    while remaining >= level * XP_PER_LEVEL {
+        remaining -= level * XP_PER_LEVEL;
+        level += 1;
+    }
+    (level, remaining, level * XP_PER_LEVEL)
+}
+
+fn level_text(localizer: &Localizer, xp: u32) -> String {
+    let (level, current, needed) = level_progress(xp);
+    localizer.format(
+        "hud.level",
+        &[("level", &level), ("xp", &current), ("needed", &needed)],
+    )
+}
+
+fn wave_text(localizer: &Localizer, wave: &WaveStarted) -> String {
+    localizer.format(
+        "hud.wave",
+        &[("wave", &(wave.wave + 1)), ("loop", &(wave.loop_index + 1))],
+    )
+}
+
+fn hud_timer(
+    mut survival_time: EventReader<SurvivalTimeChanged>,
+    mut text: Query<&mut Text, With<HudTimerText>>,
+) {
+    let Some(SurvivalTimeChanged { seconds }) = survival_time.iter().last().copied() else {
+        return;
+    };
+
+    for mut text in &mut text {
+        text.sections[0].value = format!("{:02}:{:02}", seconds / 60, seconds % 60);
+    }
+}
+
+fn hud_kills(
+    mut killed: EventReader<EnemyKilled>,
+    mut count: ResMut<EnemyCount>,
+    mut text: Query<&mut Text, With<HudKillsText>>,
+    localizer: Localizer,
+) {
+    let kills = killed.iter().count() as u32;
+    if kills == 0 && !localizer.is_changed() {
+        return;
+    }
+    count.value += kills;
+
+    for mut text in &mut text {
+        text.sections[0].value = localizer.format("hud.kills", &[("kills", &count.value)]);
+    }
+}
+
+fn hud_wave(
+    mut started: EventReader<WaveStarted>,
+    mut text: Query<&mut Text, With<HudWaveText>>,
+    mut current: Local<WaveStarted>,
+    localizer: Localizer,
+) {
+    match started.iter().last() {
+        Some(wave) => *current = *wave,
+        None if localizer.is_changed() => {}
+        None => return,
+    }
+
+    for mut text in &mut text {
+        text.sections[0].value = wave_text(&localizer, &current);
+    }
+}
+
+fn hud_progress(
+    mut progress: EventReader<PlayerProgress>,
+    mut level: Query<&mut Text, (With<HudLevelText>, Without<HudGoldText>)>,
+    mut gold: Query<&mut Text, (With<HudGoldText>, Without<HudLevelText>)>,
+    mut current: Local<PlayerProgress>,
+    localizer: Localizer,
+) {
+    match progress.iter().last() {
+        Some(progress) => *current = *progress,
+        None if localizer.is_changed() => {}
+        None => return,
+    }
+
+    for mut text in &mut level {
+        text.sections[0].value = level_text(&localizer, current.xp);
+    }
+    for mut text in &mut gold {
+        text.sections[0].value = localizer.format("hud.gold", &[("gold", &current.gold)]);
+    }
+}
+
+fn hud_dash(
+    mut changed: EventReader<DashChanged>,
+    mut text: Query<&mut Text, With<HudDashText>>,
+    mut bar: Query<(&mut Style, &mut BackgroundColor), With<HudDashBar>>,
+    mut current: Local<Option<DashChanged>>,
+    mut shown: Local<Option<(u32, u32)>>,
+    localizer: Localizer,
+) {
+    match changed.iter().last() {
+        Some(dash) => *current = Some(*dash),
+        None if localizer.is_changed() => {}
+        None => return,
+    }
+    let Some(dash) = *current else {
+        return;
+    };
+
+    for (mut style, mut color) in &mut bar {
+        style.size.width = Val::Percent(dash.recharge * 100.0);
+        color.0 = if dash.charges > 0 {
+            Color::rgb(0.3, 0.8, 1.0)
+        } else {
+            Color::rgb(0.4, 0.4, 0.5)
+        };
+    }
+
+    let charges = (dash.charges, dash.max_charges);
+    if *shown == Some(charges) && !localizer.is_changed() {
+        return;
+    }
+    *shown = Some(charges);
+    for mut text in &mut text {
+        text.sections[0].value = localizer.format(
+            "hud.dash",
+            &[("charges", &dash.charges), ("max", &dash.max_charges)],
+        );
+    }
+}
+
+fn spawn_hud(mut commands: Commands, localizer: Localizer, mut count: ResMut<EnemyCount>) {
+    count.value = 0;
+
+    let corner = |position: UiRect, align_items: AlignItems| NodeBundle {
+        style: Style {
+            position,
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            align_items,
+            ..default()
+        },
+        ..default()
+    };
+
+    let text = |value: String, font_size: f32| {
+        let style = localizer.style(&value, font_size, Color::rgb(0.9, 0.9, 0.9));
+        TextBundle::from_section(value, style)
+    };
+
+    let inset = Val::Percent(2.0);
+
+    commands
+        .spawn((
+            corner(
+                UiRect {
+                    left: inset,
+                    top: inset,
+                    ..default()
+                },
+                AlignItems::FlexStart,
+            ),
+            GamePlayEntity,
+            Name::new("HUD Top Left"),
+        ))
+        .with_children(|commands| {
+            commands.spawn((text("00:00".to_string(), 40.0), HudTimerText));
+        });
+
+    commands
+        .spawn((
+            corner(
+                UiRect {
+                    right: inset,
+                    top: inset,
+                    ..default()
+                },
+                AlignItems::FlexEnd,
+            ),
+            GamePlayEntity,
+            Name::new("HUD Top Right"),
+        ))
+        .with_children(|commands| {
+            commands.spawn((
+                text(wave_text(&localizer, &WaveStarted::default()), 32.0),
+                HudWaveText,
+            ));
+            commands.spawn((
+                text(localizer.format("hud.kills", &[("kills", &0)]), 32.0),
+                HudKillsText,
+            ));
+        });
+
+    commands
+        .spawn((
+            corner(
+                UiRect {
+                    left: inset,
+                    bottom: inset,
+                    ..default()
+                },
+                AlignItems::FlexStart,
+            ),
+            GamePlayEntity,
+            Name::new("HUD Bottom Left"),
+        ))
+        .with_children(|commands| {
+            commands.spawn((text(level_text(&localizer, 0), 32.0), HudLevelText));
+            commands.spawn((
+                text(
+                    localizer.format("hud.dash", &[("charges", &1), ("max", &1)]),
+                    24.0,
+                ),
+                HudDashText,
+            ));
+            commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(120.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                    ..default()
+                })
+                .with_children(|commands| {
+                    commands.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: Color::rgb(0.3, 0.8, 1.0).into(),
+                            ..default()
+                        },
+                        HudDashBar,
+                    ));
+                });
+        });
+
+    commands
+        .spawn((
+            corner(
+                UiRect {
+                    right: inset,
+                    bottom: inset,
+                    ..default()
+                },
+                AlignItems::FlexEnd,
+            ),
+            GamePlayEntity,
+            Name::new("HUD Bottom Right"),
+        ))
+        .with_children(|commands| {
+            commands.spawn((
+                text(localizer.format("hud.gold", &[("gold", &0)]), 32.0),
+                HudGoldText,
+            ));
+        });
+}
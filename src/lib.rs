@@ -1,28 +1,73 @@
+mod actions;
+mod aim;
 mod animation;
+mod arena;
 mod attacks;
+mod background;
 mod camera;
+mod controls;
+mod damage_numbers;
+mod elite;
 mod enemy;
+mod game;
+mod health_bar;
+mod hud;
+mod localization;
+mod loot;
+mod menu;
+mod music;
+mod pathfinding;
+mod pause;
 mod player;
+mod postprocess;
 mod potions;
+mod props;
+mod settings;
+mod sound;
+mod targeting;
 mod ui;
 
 pub mod prelude {
 
+    pub use crate::actions::{ActionSystem, ActionsPlugin};
+    pub use crate::aim::AimPlugin;
     pub use crate::animation::AnimationPlugin;
+    pub use crate::arena::ArenaPlugin;
     pub use crate::attacks::AttackPlugin;
+    pub use crate::background::BackgroundPlugin;
     pub use crate::camera::GameCameraPlugin;
+    pub use crate::controls::ControlsPlugin;
+    pub use crate::damage_numbers::DamageNumbersPlugin;
+    pub use crate::elite::ElitePlugin;
     pub use crate::enemy::EnemyPlugin;
+    pub use crate::game::{BevyQuestPlugin, BevyQuestPlugins, GameAssets};
+    pub use crate::health_bar::HealthBarPlugin;
+    pub use crate::hud::HudPlugin;
+    pub use crate::localization::{LocalizationPlugin, Localizer};
+    pub use crate::loot::LootPlugin;
+    pub use crate::menu::MenuPlugin;
+    pub use crate::music::MusicPlugin;
+    pub use crate::pathfinding::PathfindingPlugin;
+    pub use crate::pause::PausePlugin;
     pub use crate::player::PlayerPlugin;
+    pub use crate::postprocess::PostProcessPlugin;
     pub use crate::potions::PotionsPlugin;
+    pub use crate::props::PropsPlugin;
+    pub use crate::settings::SettingsPlugin;
+    pub use crate::sound::SoundPlugin;
+    pub use crate::targeting::{TargetingPlugin, TargetingStrategy};
     pub use crate::ui::GameUiPlugin;
 
     pub use bevy::prelude::*;
+    pub use bevy::reflect::TypeUuid;
     pub use bevy::time::Stopwatch;
     pub use bevy::window::PrimaryWindow;
+    #[cfg(feature = "debug_tools")]
     pub use bevy_editor_pls::prelude::*;
     pub use bevy_rapier2d::prelude::*;
     pub use bevy_turborand::prelude::*;
     pub use rand::prelude::*;
+    pub use serde::{Deserialize, Serialize};
     pub use std::collections::HashMap;
 
     pub const PLAYER_SIZE: f32 = 64.0;
@@ -37,6 +82,13 @@ pub mod prelude {
     pub const RENDER_HEIGHT: f32 = 540.;
     pub const PIXEL_TO_WORLD: f32 = 30. / 960.;
     pub const POTION_SPAWN_TIME: f32 = 1.0;
+    pub const ARENA_TILE_SIZE: f32 = 64.0 * PIXEL_TO_WORLD;
+    pub const HEAVY_HIT_DAMAGE: f32 = 10.0;
+    pub const ENEMY_DESPAWN_DISTANCE: f32 = 30.0;
+    pub const DAGGER_THROW_INTERVAL: f32 = 1.2;
+    pub const DAGGER_RANGE: f32 = 8.0;
+    pub const DAGGER_DAMAGE: f32 = 3.0;
+    pub const DAGGER_SPEED: f32 = 14.0;
 
     #[derive(States, PartialEq, Eq, Default, Debug, Clone, Hash)]
     pub enum GameState {
@@ -46,6 +98,13 @@ pub mod prelude {
         GameOver,
     }
 
+    #[derive(States, PartialEq, Eq, Default, Debug, Clone, Hash)]
+    pub enum PauseState {
+        #[default]
+        Running,
+        Paused,
+    }
+
     #[derive(Component)]
     pub struct Player {
         pub speed: f32,
@@ -54,12 +113,15 @@ pub mod prelude {
         pub damage: f32,
         pub facing: Facing,
         pub state: PlayerState,
+        pub gold: u32,
+        pub xp: u32,
     }
 
     #[derive(Component, Clone)]
     pub struct Enemy {
         pub speed: f32,
         pub health: f32,
+        pub max_health: f32,
         pub damage: f32,
         pub asset: String,
         // pub facing: Facing,
@@ -77,6 +139,167 @@ pub mod prelude {
         pub asset: String,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PropKind {
+        Crate,
+        Barrel,
+        Urn,
+    }
+
+    #[derive(Component)]
+    pub struct Prop {
+        pub kind: PropKind,
+        pub health: f32,
+    }
+
+    #[derive(Resource)]
+    pub struct PropSpawner {
+        pub timer: Timer,
+        pub max_props: usize,
+    }
+
+    impl Default for PropSpawner {
+        fn default() -> PropSpawner {
+            PropSpawner {
+                timer: Timer::from_seconds(3.0, TimerMode::Repeating),
+                max_props: 12,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PowerUp {
+        Haste,
+        Might,
+        Swiftness,
+        Focus,
+        Daggers,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Loot {
+        Potion,
+        Coins(u32),
+        Xp(u32),
+        PowerUp(PowerUp),
+    }
+
+    #[derive(Clone)]
+    pub struct DropTable {
+        pub nothing: u32,
+        pub entries: Vec<(Loot, u32)>,
+    }
+
+    #[derive(Component)]
+    pub struct Pickup(pub Loot);
+
+    #[derive(Component)]
+    pub struct Dash {
+        pub charges: u32,
+        pub max_charges: u32,
+        pub cooldown: f32,
+        pub cooldown_reduction: f32,
+        pub recharge: Timer,
+        pub speed: f32,
+        pub active: Option<(Vec2, Timer)>,
+    }
+
+    impl Default for Dash {
+        fn default() -> Dash {
+            Dash {
+                charges: 1,
+                max_charges: 1,
+                cooldown: 1.5,
+                cooldown_reduction: 0.0,
+                recharge: Timer::from_seconds(1.5, TimerMode::Once),
+                speed: 18.0,
+                active: None,
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct DashChanged {
+        pub charges: u32,
+        pub max_charges: u32,
+        /// How far the next charge has recharged, 1.0 when all charges are ready.
+        pub recharge: f32,
+    }
+
+    #[derive(Component)]
+    pub struct Invulnerable;
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct TargetCandidate {
+        pub entity: Entity,
+        pub position: Vec2,
+        pub health: f32,
+        pub max_health: f32,
+    }
+
+    #[derive(Resource)]
+    pub struct EnemyGrid {
+        pub cell_size: f32,
+        pub cells: HashMap<IVec2, Vec<TargetCandidate>>,
+    }
+
+    impl Default for EnemyGrid {
+        fn default() -> EnemyGrid {
+            EnemyGrid {
+                cell_size: 4.0,
+                cells: HashMap::new(),
+            }
+        }
+    }
+
+    #[derive(Component)]
+    pub struct DaggerThrower {
+        pub timer: Timer,
+        pub strategy: TargetingStrategy,
+        pub range: f32,
+        pub damage: f32,
+        pub speed: f32,
+    }
+
+    impl Default for DaggerThrower {
+        fn default() -> DaggerThrower {
+            DaggerThrower {
+                timer: Timer::from_seconds(DAGGER_THROW_INTERVAL, TimerMode::Repeating),
+                strategy: TargetingStrategy::Nearest,
+                range: DAGGER_RANGE,
+                damage: DAGGER_DAMAGE,
+                speed: DAGGER_SPEED,
+            }
+        }
+    }
+
+    #[derive(Component)]
+    pub struct Dagger {
+        pub velocity: Vec2,
+        pub damage: f32,
+        pub lifetime: Timer,
+        /// Everything the dagger overlaps this frame, in the order physics reported it.
+        pub touching: Vec<Entity>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum AimDevice {
+        #[default]
+        Mouse,
+        Stick,
+    }
+
+    #[derive(Resource, Default)]
+    pub struct Aim {
+        pub direction: Option<Vec2>,
+        pub device: AimDevice,
+    }
+
+    #[derive(Component, Default)]
+    pub struct ActivePowerUps {
+        pub timers: Vec<(PowerUp, Timer)>,
+    }
+
     #[derive(Component)]
     pub struct TwoFrameAnimation {
         pub frame_1: Handle<Image>,
@@ -88,9 +311,272 @@ pub mod prelude {
     #[derive(Resource)]
     pub struct AboutShown(pub bool);
 
+    #[derive(Resource)]
+    pub struct SettingsShown(pub bool);
+
+    #[derive(Resource)]
+    pub struct ControlsShown(pub bool);
+
+    #[derive(Resource, Default)]
+    pub struct RebindState {
+        pub listening: Option<Action>,
+        pub message: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WindowModeSetting {
+        Windowed,
+        Borderless,
+        Fullscreen,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AimMode {
+        Facing,
+        Free,
+    }
+
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+    #[serde(default)]
+    pub struct UserSettings {
+        pub master_volume: f32,
+        pub music_volume: f32,
+        pub sfx_volume: f32,
+        pub ui_volume: f32,
+        pub window_mode: WindowModeSetting,
+        pub vsync: bool,
+        pub screen_shake: bool,
+        pub damage_numbers: bool,
+        pub enemy_health_bars: bool,
+        pub language: String,
+        pub aim_mode: AimMode,
+    }
+
+    impl Default for UserSettings {
+        fn default() -> UserSettings {
+            UserSettings {
+                master_volume: 1.0,
+                music_volume: 0.3,
+                sfx_volume: 1.0,
+                ui_volume: 0.8,
+                window_mode: WindowModeSetting::Windowed,
+                vsync: true,
+                screen_shake: true,
+                damage_numbers: true,
+                enemy_health_bars: true,
+                language: "en".to_string(),
+                aim_mode: AimMode::Facing,
+            }
+        }
+    }
+
+    impl UserSettings {
+        pub fn music(&self) -> f32 {
+            self.master_volume * self.music_volume
+        }
+
+        pub fn sfx(&self) -> f32 {
+            self.master_volume * self.sfx_volume
+        }
+
+        pub fn ui(&self) -> f32 {
+            self.master_volume * self.ui_volume
+        }
+
+        pub fn volume(&self, category: AudioCategory) -> f32 {
+            match category {
+                AudioCategory::Music => self.music(),
+                AudioCategory::Sfx => self.sfx(),
+                AudioCategory::Ui => self.ui(),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AudioCategory {
+        Music,
+        Sfx,
+        Ui,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Sfx {
+        Slice,
+        Explosion,
+        Coin,
+        PowerUp,
+        Potion,
+        PropBreak,
+        GameOver,
+        MenuSelect,
+        EnemyAttack,
+        EnemyDeath,
+    }
+
+    pub struct PlaySfx {
+        pub sfx: Sfx,
+        /// World position to pan and attenuate from, relative to the main camera.
+        pub position: Option<Vec2>,
+    }
+
+    impl PlaySfx {
+        pub fn global(sfx: Sfx) -> PlaySfx {
+            PlaySfx {
+                sfx,
+                position: None,
+            }
+        }
+
+        pub fn at(sfx: Sfx, position: Vec3) -> PlaySfx {
+            PlaySfx {
+                sfx,
+                position: Some(position.truncate()),
+            }
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct SfxVoices(pub HashMap<Sfx, Vec<f64>>);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MusicTrack {
+        Menu,
+        GamePlay,
+        Boss,
+        GameOver,
+    }
+
+    pub struct MusicVoice {
+        pub track: MusicTrack,
+        pub sink: Handle<AudioSink>,
+        pub fade: f32,
+        pub started: f64,
+    }
+
+    #[derive(Resource, Default)]
+    pub struct MusicDirector {
+        pub current: Option<MusicVoice>,
+        pub fading_out: Vec<MusicVoice>,
+        pub intensity: f32,
+    }
+
+    /// Enemy waves and potion spawns the game runs with.
+    #[derive(Clone)]
+    pub struct GameContent {
+        pub waves: Vec<Respawn>,
+        pub potions: Vec<RePotion>,
+    }
+
+    #[derive(Clone)]
+    pub enum ContentSource {
+        BuiltIn,
+        Custom(GameContent),
+    }
+
+    #[derive(Clone)]
+    pub struct BevyQuestConfig {
+        pub content: ContentSource,
+        /// Seeds the global RNG for reproducible runs.
+        pub seed: Option<u64>,
+        /// Adds the in-game editor when built with the `debug_tools` feature.
+        pub debug_tools: bool,
+        pub audio: bool,
+        pub window: Window,
+        /// Runs only the gameplay on `MinimalPlugins`: no window, renderer, audio or UI.
+        pub headless: bool,
+    }
+
+    /// Present when the game runs headless.
+    #[derive(Resource)]
+    pub struct Headless;
+
     #[derive(Resource)]
     pub struct MainRender(pub Handle<Image>);
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RenderScaling {
+        PixelPerfect,
+        Fit,
+        Stretch,
+    }
+
+    #[derive(Resource, Clone)]
+    pub struct RenderSettings {
+        pub width: u32,
+        pub height: u32,
+        pub scaling: RenderScaling,
+    }
+
+    impl Default for RenderSettings {
+        fn default() -> RenderSettings {
+            RenderSettings {
+                width: RENDER_WIDTH as u32,
+                height: RENDER_HEIGHT as u32,
+                scaling: RenderScaling::Fit,
+            }
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct RenderViewport {
+        pub rect: Rect,
+    }
+
+    #[derive(Component)]
+    pub struct BaseRender;
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct ColorGrading {
+        pub tint: Color,
+        pub saturation: f32,
+        pub contrast: f32,
+        pub brightness: f32,
+    }
+
+    impl Default for ColorGrading {
+        fn default() -> ColorGrading {
+            ColorGrading {
+                tint: Color::WHITE,
+                saturation: 1.0,
+                contrast: 1.0,
+                brightness: 0.0,
+            }
+        }
+    }
+
+    #[derive(Resource)]
+    pub struct PostProcessSettings {
+        pub vignette: f32,
+        pub flash: f32,
+        pub flash_color: Color,
+        pub flash_decay: f32,
+        pub crt: f32,
+        pub grading: ColorGrading,
+        pub biome_grading: Vec<ColorGrading>,
+    }
+
+    impl Default for PostProcessSettings {
+        fn default() -> PostProcessSettings {
+            PostProcessSettings {
+                vignette: 0.0,
+                flash: 0.0,
+                flash_color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                flash_decay: 4.0,
+                crt: 0.0,
+                grading: ColorGrading::default(),
+                biome_grading: vec![
+                    ColorGrading::default(),
+                    ColorGrading {
+                        tint: Color::rgb(1.05, 0.97, 0.88),
+                        saturation: 0.9,
+                        contrast: 1.05,
+                        brightness: 0.0,
+                    },
+                ],
+            }
+        }
+    }
+
     #[derive(Component)]
     pub struct HeaderBarUI;
 
@@ -110,22 +596,120 @@ pub mod prelude {
     pub struct PauseUI;
 
     #[derive(Component)]
-    pub struct StartButtonUI;
+    pub struct GamePlayEntity;
 
     #[derive(Component)]
-    pub struct AboutButtonUI;
+    pub struct SettingsUI;
 
     #[derive(Component)]
-    pub struct GamePlayEntity;
+    pub struct ControlsUI;
 
     #[derive(Component)]
-    pub struct GameOverButtonUI;
+    pub struct RebindRow(pub Action);
 
     #[derive(Component)]
-    pub struct AboutUI;
+    pub struct RebindMessageText;
+
+    pub const MENU_LAYER: u8 = 0;
+    pub const ABOUT_LAYER: u8 = 1;
+    pub const SETTINGS_LAYER: u8 = 2;
+    pub const CONTROLS_LAYER: u8 = 3;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MenuAction {
+        StartGame,
+        ShowAbout,
+        CloseAbout,
+        BackToMenu,
+        Resume,
+        AbandonRun,
+        OpenSettings,
+        CloseSettings,
+        CycleSetting(SettingsOption),
+        OpenControls,
+        CloseControls,
+        Rebind(Action),
+        ResetBindings,
+    }
+
+    impl MenuAction {
+        pub fn is_back(&self) -> bool {
+            matches!(
+                self,
+                MenuAction::CloseAbout
+                    | MenuAction::CloseSettings
+                    | MenuAction::CloseControls
+                    | MenuAction::Resume
+                    | MenuAction::BackToMenu
+            )
+        }
+    }
+
+    #[derive(Component)]
+    pub struct MenuButton {
+        pub action: MenuAction,
+        pub layer: u8,
+    }
+
+    #[derive(Resource, Default)]
+    pub struct MenuFocus(pub Option<Entity>);
+
+    pub struct MenuActivated(pub MenuAction);
+
+    #[derive(Resource)]
+    pub struct MenuTheme {
+        pub idle: Color,
+        pub focused: Color,
+        pub pressed: Color,
+        pub text: Color,
+    }
+
+    impl Default for MenuTheme {
+        fn default() -> MenuTheme {
+            MenuTheme {
+                idle: Color::DARK_GREEN,
+                focused: Color::GREEN,
+                pressed: Color::RED,
+                text: Color::rgb(0.9, 0.9, 0.9),
+            }
+        }
+    }
+
+    #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SettingsOption {
+        MasterVolume,
+        MusicVolume,
+        SfxVolume,
+        UiVolume,
+        WindowMode,
+        Vsync,
+        ScreenShake,
+        DamageNumbers,
+        EnemyHealthBars,
+        Language,
+        AimMode,
+    }
+
+    #[derive(TypeUuid, Deserialize, Debug, Clone)]
+    #[uuid = "0d3f9a52-7c1e-4b8a-a6f4-5e2c81b7d903"]
+    pub struct Locale {
+        pub name: String,
+        #[serde(default)]
+        pub font: Option<String>,
+        pub strings: HashMap<String, String>,
+    }
+
+    #[derive(Resource)]
+    pub struct Localization {
+        pub language: String,
+        pub locales: HashMap<String, Handle<Locale>>,
+    }
 
     #[derive(Component)]
-    pub struct AboutBackButton;
+    pub struct LocalizedText(pub Vec<&'static str>);
+
+    #[derive(Component)]
+    pub struct AboutUI;
 
     #[derive(Component)]
     pub struct MainCamera;
@@ -133,11 +717,83 @@ pub mod prelude {
     #[derive(Component)]
     pub struct FinalCamera;
 
+    #[derive(Component, Default)]
+    pub struct CameraRig {
+        pub focus: Vec2,
+        pub look_ahead: Vec2,
+        pub last_target: Option<Vec2>,
+    }
+
+    #[derive(Resource)]
+    pub struct CameraController {
+        pub smoothing: f32,
+        pub dead_zone: Vec2,
+        pub look_ahead: f32,
+        pub clamp_to_arena: bool,
+        pub shake_enabled: bool,
+    }
+
+    impl Default for CameraController {
+        fn default() -> CameraController {
+            CameraController {
+                smoothing: 8.0,
+                dead_zone: Vec2::new(1.0, 0.75),
+                look_ahead: 2.0,
+                clamp_to_arena: true,
+                shake_enabled: true,
+            }
+        }
+    }
+
+    #[derive(Resource)]
+    pub struct CameraShake {
+        pub trauma: f32,
+        pub decay: f32,
+        pub max_offset: f32,
+        pub max_angle: f32,
+        pub time: f32,
+    }
+
+    impl Default for CameraShake {
+        fn default() -> CameraShake {
+            CameraShake {
+                trauma: 0.0,
+                decay: 1.2,
+                max_offset: 0.6,
+                max_angle: 0.05,
+                time: 0.0,
+            }
+        }
+    }
+
+    pub struct ScreenShake(pub f32);
+
     #[derive(Component)]
-    pub struct WorldTextUI {
+    pub struct DamageNumber {
+        pub target: Option<Entity>,
+        pub amount: f32,
         pub lifetime: Timer,
-        pub velocity: Vec2,
-        pub position: Vec2,
+    }
+
+    #[derive(Component, Default, Clone, Copy)]
+    pub struct DamageNumberFade(pub f32);
+
+    #[derive(Resource, Default)]
+    pub struct DamageNumberPool {
+        pub free: Vec<Entity>,
+        pub active: HashMap<Entity, Entity>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum DamageText {
+        Amount(f32),
+        Label(String),
+    }
+
+    pub struct DamageNumberEvent {
+        pub target: Option<Entity>,
+        pub position: Vec3,
+        pub text: DamageText,
     }
 
     #[derive(Resource)]
@@ -153,35 +809,171 @@ pub mod prelude {
         }
     }
 
-    #[derive(Resource)]
+    #[derive(Resource, Clone)]
     pub struct SpawnManager {
         pub global_time: Stopwatch,
         pub waves: Vec<Respawn>,
     }
 
+    #[derive(Clone)]
     pub struct Respawn {
         pub next_spawn: Timer,
         pub respawn_size: i32,
         pub to_spawn: Enemy,
+        pub elite_chance: f32,
+        pub elite_affixes: Vec<Affix>,
     }
 
-    #[derive(Resource)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Affix {
+        Fast,
+        Armored,
+        Vampiric,
+        Explosive,
+        Splitting,
+        Shielded,
+    }
+
+    #[derive(Component)]
+    pub struct HealthBar {
+        pub background: Entity,
+        pub fill: Entity,
+        pub width: f32,
+        pub last_health: f32,
+        pub shown: Timer,
+    }
+
+    #[derive(Component, Clone)]
+    pub struct Elite {
+        pub affixes: Vec<Affix>,
+        pub shield: f32,
+    }
+
+    #[derive(Component)]
+    pub struct EliteNameTag;
+
+    #[derive(Resource, Clone)]
     pub struct PotionManager {
         pub potion_time: Stopwatch,
         pub potion_waves: Vec<RePotion>,
     }
 
+    #[derive(Clone)]
     pub struct RePotion {
         pub next_potion_spawn: Timer,
         pub potion_count: i32,
         pub to_spawn_potion: Potion,
     }
 
+    #[derive(Component)]
+    pub struct BackgroundTile {
+        pub chunk: IVec2,
+    }
+
+    #[derive(Resource)]
+    pub struct BackgroundChunks {
+        pub size: f32,
+        pub radius: i32,
+        pub seed: u64,
+        pub variants: Vec<String>,
+    }
+
+    impl Default for BackgroundChunks {
+        fn default() -> BackgroundChunks {
+            BackgroundChunks {
+                size: 1080.0 * PIXEL_TO_WORLD,
+                radius: 1,
+                seed: 0,
+                variants: vec!["map.png".to_string(), "background.png".to_string()],
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ArenaTile {
+        Floor,
+        Wall,
+        Obstacle,
+        SpawnPoint,
+        PickupPoint,
+        PlayerStart,
+    }
+
+    #[derive(TypeUuid, Debug, Clone)]
+    #[uuid = "6b1f4f8e-3c8a-4f57-9a53-2f0f6b2d9c41"]
+    pub struct Arena {
+        pub width: i32,
+        pub height: i32,
+        pub tiles: Vec<ArenaTile>,
+    }
+
+    #[derive(Resource)]
+    pub struct CurrentArena(pub Handle<Arena>);
+
+    #[derive(Component)]
+    pub struct ArenaBlock;
+
+    #[derive(Resource, Default)]
+    pub struct FlowField {
+        pub target: Option<IVec2>,
+        pub width: i32,
+        pub height: i32,
+        pub distance: Vec<u32>,
+    }
+
     #[derive(Resource, Default)]
     pub struct EnemyCount {
         pub value: u32,
     }
 
+    pub struct EnemyKilled {
+        pub position: Vec3,
+        pub asset: String,
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct PlayerDamaged {
+        pub amount: f32,
+    }
+
+    #[derive(Default, Clone, Copy)]
+    pub struct WaveStarted {
+        pub wave: usize,
+        pub loop_index: usize,
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct SurvivalTimeChanged {
+        pub seconds: u64,
+    }
+
+    #[derive(Default, Clone, Copy)]
+    pub struct PlayerProgress {
+        pub gold: u32,
+        pub xp: u32,
+    }
+
+    #[derive(Component)]
+    pub struct HudTimerText;
+
+    #[derive(Component)]
+    pub struct HudDashText;
+
+    #[derive(Component)]
+    pub struct HudDashBar;
+
+    #[derive(Component)]
+    pub struct HudWaveText;
+
+    #[derive(Component)]
+    pub struct HudKillsText;
+
+    #[derive(Component)]
+    pub struct HudLevelText;
+
+    #[derive(Component)]
+    pub struct HudGoldText;
+
     #[derive(Debug, Hash, PartialEq, Eq)]
     pub enum Animation {
         PlayerIdle,
@@ -201,15 +993,82 @@ pub mod prelude {
         NagaDown,
     }
 
-    #[derive(Component, Debug, Hash, PartialEq, Eq)]
+    #[derive(Component, Debug, Hash, PartialEq, Eq, Clone, Copy)]
     pub enum Facing {
         Left,
         Right,
         Up,
         Down,
+        UpLeft,
+        UpRight,
+        DownLeft,
+        DownRight,
+    }
+
+    impl Facing {
+        pub fn from_direction(direction: Vec2) -> Option<Facing> {
+            if direction.length_squared() < 1e-4 {
+                return None;
+            }
+            let octant = (direction.y.atan2(direction.x) / std::f32::consts::FRAC_PI_4).round();
+            Some(match (octant as i32).rem_euclid(8) {
+                0 => Facing::Right,
+                1 => Facing::UpRight,
+                2 => Facing::Up,
+                3 => Facing::UpLeft,
+                4 => Facing::Left,
+                5 => Facing::DownLeft,
+                6 => Facing::Down,
+                _ => Facing::DownRight,
+            })
+        }
+
+        pub fn direction(&self) -> Vec2 {
+            let direction = match self {
+                Facing::Left => Vec2::new(-1.0, 0.0),
+                Facing::Right => Vec2::new(1.0, 0.0),
+                Facing::Up => Vec2::new(0.0, 1.0),
+                Facing::Down => Vec2::new(0.0, -1.0),
+                Facing::UpLeft => Vec2::new(-1.0, 1.0),
+                Facing::UpRight => Vec2::new(1.0, 1.0),
+                Facing::DownLeft => Vec2::new(-1.0, -1.0),
+                Facing::DownRight => Vec2::new(1.0, -1.0),
+            };
+            direction.normalize()
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Action {
+        MoveUp,
+        MoveDown,
+        MoveLeft,
+        MoveRight,
+        Attack,
+        Dash,
+        Pause,
+        Confirm,
+        Cancel,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum InputBinding {
+        Key(KeyCode),
+        Gamepad(GamepadButtonType),
+    }
+
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct InputBindings(pub HashMap<Action, Vec<InputBinding>>);
+
+    #[derive(Resource, Default)]
+    pub struct ActionState {
+        pub movement: Vec2,
+        pub aim: Vec2,
+        pub pressed: Vec<Action>,
+        pub previous: Vec<Action>,
     }
 
-    #[derive(Component, Debug, Hash, PartialEq, Eq)]
+    #[derive(Component, Debug, Hash, PartialEq, Eq, Clone, Copy)]
     pub enum PlayerState {
         Moving,
         Idle,
@@ -0,0 +1,222 @@
+use crate::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    ecs::system::SystemParam,
+    utils::BoxedFuture,
+};
+use std::fmt::Display;
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Locale>()
+            .init_asset_loader::<LocaleLoader>()
+            .init_resource::<Localization>()
+            .add_systems((apply_language, locales_loaded, relocalize_texts).chain());
+    }
+}
+
+pub const LANGUAGES: [&str; 2] = ["en", "de"];
+const FALLBACK_LANGUAGE: &str = "en";
+const DEFAULT_FONT: &str = "fonts/dos.ttf";
+
+impl FromWorld for Localization {
+    fn from_world(world: &mut World) -> Localization {
+        let assets = world.resource::<AssetServer>();
+        Localization {
+            language: FALLBACK_LANGUAGE.to_string(),
+            locales: LANGUAGES
+                .iter()
+                .map(|language| {
+                    let handle = assets.load(format!("locales/{language}.locale"));
+                    (language.to_string(), handle)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct LocaleLoader;
+
+impl AssetLoader for LocaleLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let locale: Locale = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(locale));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["locale"]
+    }
+}
+
+// dos.ttf is a code page 437 font: ASCII plus these characters for bytes 0x80 to 0xFF.
+const CP437_UPPER: &str = concat!(
+    "ÇüéâäàåçêëèïîìÄÅ",
+    "ÉæÆôöòûùÿÖÜ¢£¥₧ƒ",
+    "áíóúñÑªº¿⌐¬½¼¡«»",
+    "░▒▓│┤╡╢╖╕╣║╗╝╜╛┐",
+    "└┴┬├─┼╞╟╚╔╩╦╠═╬╧",
+    "╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀",
+    "αßΓπΣσµτΦΘΩδ∞φε∩",
+    "≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}",
+);
+
+fn dos_font_supports(c: char) -> bool {
+    c.is_ascii() || CP437_UPPER.contains(c)
+}
+
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    localization: Res<'w, Localization>,
+    locales: Res<'w, Assets<Locale>>,
+    assets: Res<'w, AssetServer>,
+}
+
+impl<'w> Localizer<'w> {
+    fn locale(&self, language: &str) -> Option<&Locale> {
+        self.localization
+            .locales
+            .get(language)
+            .and_then(|handle| self.locales.get(handle))
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.localization.is_changed()
+    }
+
+    pub fn text(&self, key: &str) -> String {
+        [self.localization.language.as_str(), FALLBACK_LANGUAGE]
+            .iter()
+            .find_map(|language| self.locale(language)?.strings.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter().fold(self.text(key), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+    }
+
+    pub fn language_name(&self, language: &str) -> String {
+        self.locale(language)
+            .map(|locale| locale.name.clone())
+            .unwrap_or_else(|| language.to_uppercase())
+    }
+
+    pub fn font(&self, text: &str) -> Handle<Font> {
+        if text.chars().all(dos_font_supports) {
+            return self.assets.load(DEFAULT_FONT);
+        }
+        let fallback = self
+            .locale(&self.localization.language)
+            .and_then(|locale| locale.font.clone())
+            .or_else(|| {
+                LANGUAGES
+                    .iter()
+                    .find_map(|language| self.locale(language)?.font.clone())
+            });
+        match fallback {
+            Some(font) => self.assets.load(font),
+            None => {
+                warn!("No font available for {text:?}, falling back to {DEFAULT_FONT}");
+                self.assets.load(DEFAULT_FONT)
+            }
+        }
+    }
+
+    pub fn style(&self, text: &str, font_size: f32, color: Color) -> TextStyle {
+        TextStyle {
+            font: self.font(text),
+            font_size,
+            color,
+        }
+    }
+
+    pub fn localized(
+        &self,
+        key: &'static str,
+        font_size: f32,
+        color: Color,
+    ) -> (TextBundle, LocalizedText) {
+        let text = self.text(key);
+        let style = self.style(&text, font_size, color);
+        (
+            TextBundle::from_section(text, style),
+            LocalizedText(vec![key]),
+        )
+    }
+}
+
+fn apply_language(settings: Res<UserSettings>, mut localization: ResMut<Localization>) {
+    if settings.is_changed() && localization.language != settings.language {
+        localization.language = settings.language.clone();
+    }
+}
+
+fn locales_loaded(
+    mut events: EventReader<AssetEvent<Locale>>,
+    mut localization: ResMut<Localization>,
+) {
+    if events.iter().count() > 0 {
+        localization.set_changed();
+    }
+}
+
+fn relocalize_texts(localizer: Localizer, mut texts: Query<(&LocalizedText, &mut Text)>) {
+    if !localizer.is_changed() {
+        return;
+    }
+    for (localized, mut text) in &mut texts {
+        for (section, key) in text.sections.iter_mut().zip(&localized.0) {
+            section.value = localizer.text(key);
+            section.style.font = localizer.font(&section.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(language: &str) -> Locale {
+        let path = format!(
+            "{}/assets/locales/{language}.locale",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let source = std::fs::read_to_string(&path).unwrap();
+        ron::from_str(&source).unwrap()
+    }
+
+    #[test]
+    fn dos_font_covers_code_page_437_only() {
+        assert_eq!(CP437_UPPER.chars().count(), 128);
+        assert!("Über Lautstärke".chars().all(dos_font_supports));
+        assert!(!dos_font_supports('©'));
+        assert!(!dos_font_supports('À'));
+    }
+
+    #[test]
+    fn every_locale_translates_every_english_key() {
+        let english = load(FALLBACK_LANGUAGE);
+        for language in LANGUAGES {
+            let locale = load(language);
+            let mut missing: Vec<&String> = english
+                .strings
+                .keys()
+                .filter(|key| !locale.strings.contains_key(*key))
+                .collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{language} is missing {missing:?}");
+        }
+    }
+}
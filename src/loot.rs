@@ -0,0 +1,312 @@
+use crate::{potions::spawn_potion_entity, prelude::*};
+use bevy::{ecs::system::EntityCommands, render::texture::DEFAULT_IMAGE_HANDLE};
+
+pub struct LootPlugin;
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerProgress>()
+            .add_systems((collect_pickups, tick_power_ups).in_set(OnUpdate(GameState::GamePlay)));
+    }
+}
+
+const POWER_UP_DURATION: f32 = 10.0;
+
+impl DropTable {
+    pub fn elite() -> DropTable {
+        DropTable {
+            nothing: 0,
+            entries: vec![
+                (Loot::Potion, 3),
+                (Loot::Coins(15), 3),
+                (Loot::Xp(10), 3),
+                (Loot::PowerUp(PowerUp::Haste), 1),
+                (Loot::PowerUp(PowerUp::Might), 1),
+                (Loot::PowerUp(PowerUp::Swiftness), 1),
+                (Loot::PowerUp(PowerUp::Focus), 1),
+                (Loot::PowerUp(PowerUp::Daggers), 1),
+            ],
+        }
+    }
+
+    pub fn roll(&self, rng: &mut impl DelegatedRng) -> Option<Loot> {
+        let total = self.nothing + self.entries.iter().map(|(_, weight)| weight).sum::<u32>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.u32(0..total);
+        if roll < self.nothing {
+            return None;
+        }
+        roll -= self.nothing;
+
+        for (loot, weight) in &self.entries {
+            if roll < *weight {
+                return Some(*loot);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+impl PowerUp {
+    fn asset(&self) -> &'static str {
+        match self {
+            PowerUp::Haste | PowerUp::Swiftness => "sprites/mana_potion.png",
+            PowerUp::Might | PowerUp::Focus => "sprites/oni_mask.png",
+            PowerUp::Daggers => "sprites/GuttyKreum_1.png",
+        }
+    }
+
+    fn tint(&self) -> Color {
+        match self {
+            PowerUp::Haste | PowerUp::Might | PowerUp::Daggers => Color::WHITE,
+            PowerUp::Swiftness => Color::rgb(0.5, 1.0, 0.5),
+            PowerUp::Focus => Color::rgb(0.5, 0.9, 1.0),
+        }
+    }
+}
+
+pub fn spawn_loot(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    global_rng: &mut GlobalRng,
+    potion_manager: &PotionManager,
+    loot: Loot,
+    translation: Vec3,
+) {
+    let (texture, size) = match loot {
+        Loot::Potion => {
+            if let Some(drop) = potion_manager.potion_waves.first() {
+                spawn_potion_entity(
+                    commands,
+                    assets,
+                    global_rng,
+                    drop.to_spawn_potion.clone(),
+                    translation,
+                );
+            }
+            return;
+        }
+        Loot::Coins(_) => (assets.load("sprites/coin_1.png"), 24.0),
+        Loot::Xp(_) => (DEFAULT_IMAGE_HANDLE.typed(), 12.0),
+        Loot::PowerUp(power_up) => (assets.load(power_up.asset()), 32.0),
+    };
+
+    let color = match loot {
+        Loot::Xp(_) => Color::rgb(0.3, 0.6, 1.0),
+        Loot::PowerUp(power_up) => power_up.tint(),
+        _ => Color::WHITE,
+    };
+
+    let mut entity = commands.spawn((
+        SpriteBundle {
+            texture,
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(size * PIXEL_TO_WORLD)),
+                ..default()
+            },
+            transform: Transform::from_translation(translation),
+            ..default()
+        },
+        Pickup(loot),
+        Name::new("Pickup"),
+        Sensor,
+        Collider::ball(0.5),
+        GamePlayEntity,
+    ));
+
+    if let Loot::Coins(_) = loot {
+        entity.insert(TwoFrameAnimation {
+            frame_1: assets.load("sprites/coin_1.png"),
+            frame_2: assets.load("sprites/coin_2.png"),
+            current_frame: false,
+            timer: Timer::from_seconds(0.3, TimerMode::Repeating),
+        });
+    }
+}
+
+fn apply_power_up(
+    mut commands: EntityCommands,
+    player: &mut Player,
+    dash: &mut Dash,
+    blades: &mut Query<&mut Blade>,
+    power_up: PowerUp,
+    on: bool,
+) {
+    let factor = if on { 1.0 } else { -1.0 };
+    match power_up {
+        PowerUp::Haste => player.speed *= 1.5_f32.powf(factor),
+        PowerUp::Might => {
+            for mut blade in blades.iter_mut() {
+                blade.damage *= 2.0_f32.powf(factor);
+            }
+        }
+        PowerUp::Swiftness => {
+            if on {
+                dash.max_charges += 1;
+                dash.charges += 1;
+            } else {
+                dash.max_charges -= 1;
+                dash.charges = dash.charges.min(dash.max_charges);
+            }
+        }
+        PowerUp::Focus => dash.cooldown_reduction += 0.4 * factor,
+        PowerUp::Daggers => {
+            if on {
+                commands.insert(DaggerThrower::default());
+            } else {
+                commands.remove::<DaggerThrower>();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn collect_pickups(
+    mut commands: Commands,
+    pickups: Query<(Entity, &Collider, &GlobalTransform, &Pickup)>,
+    mut player: Query<(&mut Player, &mut Dash, &mut ActivePowerUps)>,
+    mut blades: Query<&mut Blade>,
+    rapier_context: Res<RapierContext>,
+    mut sfx: EventWriter<PlaySfx>,
+    mut progress: EventWriter<PlayerProgress>,
+) {
+    for (pickup_entity, collider, transform, pickup) in &pickups {
+        rapier_context.intersections_with_shape(
+            transform.translation().truncate(),
+            0.0,
+            collider,
+            QueryFilter::new(),
+            |entity| {
+                let Ok((mut player, mut dash, mut power_ups)) = player.get_mut(entity) else {
+                    return true;
+                };
+
+                match pickup.0 {
+                    Loot::Coins(amount) => {
+                        player.gold += amount;
+                        sfx.send(PlaySfx::at(Sfx::Coin, transform.translation()));
+                    }
+                    Loot::Xp(amount) => player.xp += amount,
+                    Loot::PowerUp(power_up) => {
+                        let timer = Timer::from_seconds(POWER_UP_DURATION, TimerMode::Once);
+                        match power_up_timer(&mut power_ups, power_up) {
+                            Some(active) => *active = timer,
+                            None => {
+                                apply_power_up(
+                                    commands.entity(entity),
+                                    &mut player,
+                                    &mut dash,
+                                    &mut blades,
+                                    power_up,
+                                    true,
+                                );
+                                power_ups.timers.push((power_up, timer));
+                            }
+                        }
+                        sfx.send(PlaySfx::at(Sfx::PowerUp, transform.translation()));
+                    }
+                    Loot::Potion => {}
+                }
+
+                if let Loot::Coins(_) | Loot::Xp(_) = pickup.0 {
+                    progress.send(PlayerProgress {
+                        gold: player.gold,
+                        xp: player.xp,
+                    });
+                }
+
+                commands.entity(pickup_entity).despawn_recursive();
+                false
+            },
+        );
+    }
+}
+
+fn power_up_timer(power_ups: &mut ActivePowerUps, power_up: PowerUp) -> Option<&mut Timer> {
+    power_ups
+        .timers
+        .iter_mut()
+        .find(|(active, _)| *active == power_up)
+        .map(|(_, timer)| timer)
+}
+
+fn tick_power_ups(
+    mut commands: Commands,
+    mut player: Query<(Entity, &mut Player, &mut Dash, &mut ActivePowerUps)>,
+    mut blades: Query<&mut Blade>,
+    time: Res<Time>,
+) {
+    for (entity, mut player, mut dash, mut power_ups) in &mut player {
+        for (_, timer) in power_ups.timers.iter_mut() {
+            timer.tick(time.delta());
+        }
+
+        let expired: Vec<PowerUp> = power_ups
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.finished())
+            .map(|(power_up, _)| *power_up)
+            .collect();
+
+        for power_up in expired {
+            apply_power_up(
+                commands.entity(entity),
+                &mut player,
+                &mut dash,
+                &mut blades,
+                power_up,
+                false,
+            );
+        }
+        power_ups.timers.retain(|(_, timer)| !timer.finished());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(table: &DropTable, rolls: usize, loot: Option<Loot>) -> usize {
+        let mut rng = RngComponent::with_seed(42);
+        (0..rolls).filter(|_| table.roll(&mut rng) == loot).count()
+    }
+
+    #[test]
+    fn empty_table_drops_nothing() {
+        let table = DropTable {
+            nothing: 0,
+            entries: Vec::new(),
+        };
+        assert_eq!(count(&table, 100, None), 100);
+    }
+
+    #[test]
+    fn single_entry_always_drops() {
+        let table = DropTable {
+            nothing: 0,
+            entries: vec![(Loot::Coins(5), 1)],
+        };
+        assert_eq!(count(&table, 100, Some(Loot::Coins(5))), 100);
+    }
+
+    #[test]
+    fn drops_follow_their_weights() {
+        let table = DropTable {
+            nothing: 1,
+            entries: vec![(Loot::Potion, 2), (Loot::Xp(1), 5)],
+        };
+        for (loot, expected) in [
+            (None, 1000),
+            (Some(Loot::Potion), 2000),
+            (Some(Loot::Xp(1)), 5000),
+        ] {
+            let count = count(&table, 8000, loot) as i32;
+            assert!((count - expected).abs() < 250, "{loot:?}: {count}");
+        }
+    }
+}
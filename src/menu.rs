@@ -0,0 +1,170 @@
+use crate::prelude::*;
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuTheme>()
+            .init_resource::<MenuFocus>()
+            .add_event::<MenuActivated>()
+            .add_systems(
+                (
+                    menu_focus_hover,
+                    menu_navigation.after(menu_focus_hover),
+                    menu_activate.after(menu_navigation),
+                    menu_button_theme.after(menu_activate),
+                )
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(bevy::ui::UiSystem::Focus)
+                    .after(ActionSystem),
+            );
+    }
+}
+
+pub fn menu_button(theme: &MenuTheme, action: MenuAction, layer: u8, size: Size) -> impl Bundle {
+    (
+        ButtonBundle {
+            style: Style {
+                size,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                align_self: AlignSelf::Center,
+                ..default()
+            },
+            background_color: theme.idle.into(),
+            ..default()
+        },
+        MenuButton { action, layer },
+    )
+}
+
+pub fn menu_text(
+    theme: &MenuTheme,
+    localizer: &Localizer,
+    key: &'static str,
+    font_size: f32,
+) -> impl Bundle {
+    localizer.localized(key, font_size, theme.text)
+}
+
+type MenuButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static MenuButton,
+        &'static ComputedVisibility,
+        &'static GlobalTransform,
+    ),
+>;
+
+fn navigable(buttons: &MenuButtons) -> Vec<Entity> {
+    let layer = buttons
+        .iter()
+        .filter(|(_, _, visibility, _)| visibility.is_visible())
+        .map(|(_, button, _, _)| button.layer)
+        .max();
+    let mut entries: Vec<(Entity, Vec3)> = buttons
+        .iter()
+        .filter(|(_, button, visibility, _)| visibility.is_visible() && Some(button.layer) == layer)
+        .map(|(entity, _, _, transform)| (entity, transform.translation()))
+        .collect();
+    entries.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    entries.into_iter().map(|(entity, _)| entity).collect()
+}
+
+fn menu_focus_hover(
+    buttons: MenuButtons,
+    changed: Query<(Entity, &Interaction), (Changed<Interaction>, With<MenuButton>)>,
+    mut focus: ResMut<MenuFocus>,
+) {
+    let menu = navigable(&buttons);
+    for (entity, interaction) in &changed {
+        if *interaction == Interaction::Hovered && menu.contains(&entity) {
+            focus.0 = Some(entity);
+        }
+    }
+    if focus.0.is_some_and(|focused| !menu.contains(&focused)) {
+        focus.0 = None;
+    }
+}
+
+fn menu_navigation(buttons: MenuButtons, actions: Res<ActionState>, mut focus: ResMut<MenuFocus>) {
+    let step: isize =
+        if actions.just_pressed(Action::MoveDown) || actions.just_pressed(Action::MoveRight) {
+            1
+        } else if actions.just_pressed(Action::MoveUp) || actions.just_pressed(Action::MoveLeft) {
+            -1
+        } else {
+            return;
+        };
+
+    let menu = navigable(&buttons);
+    if menu.is_empty() {
+        return;
+    }
+
+    let next = match focus
+        .0
+        .and_then(|focused| menu.iter().position(|entity| *entity == focused))
+    {
+        Some(index) => (index as isize + step).rem_euclid(menu.len() as isize) as usize,
+        None if step > 0 => 0,
+        None => menu.len() - 1,
+    };
+    focus.0 = Some(menu[next]);
+}
+
+fn menu_activate(
+    buttons: MenuButtons,
+    clicked: Query<(Entity, &MenuButton, &Interaction), Changed<Interaction>>,
+    actions: Res<ActionState>,
+    focus: Res<MenuFocus>,
+    mut activated: EventWriter<MenuActivated>,
+) {
+    let menu = navigable(&buttons);
+
+    for (entity, button, interaction) in &clicked {
+        if *interaction == Interaction::Clicked && menu.contains(&entity) {
+            activated.send(MenuActivated(button.action));
+            return;
+        }
+    }
+
+    if actions.just_pressed(Action::Cancel) {
+        let back = menu
+            .iter()
+            .filter_map(|entity| buttons.get(*entity).ok())
+            .find(|(_, button, _, _)| button.action.is_back());
+        if let Some((_, button, _, _)) = back {
+            activated.send(MenuActivated(button.action));
+        }
+        return;
+    }
+
+    if !actions.just_pressed(Action::Confirm) {
+        return;
+    }
+
+    if let Some((_, button, _, _)) = focus.0.and_then(|focused| buttons.get(focused).ok()) {
+        activated.send(MenuActivated(button.action));
+    }
+}
+
+fn menu_button_theme(
+    mut buttons: Query<(Entity, &Interaction, &mut BackgroundColor), With<MenuButton>>,
+    focus: Res<MenuFocus>,
+    theme: Res<MenuTheme>,
+) {
+    for (entity, interaction, mut color) in &mut buttons {
+        let target = match *interaction {
+            Interaction::Clicked => theme.pressed,
+            _ if focus.0 == Some(entity) => theme.focused,
+            Interaction::Hovered => theme.focused,
+            Interaction::None => theme.idle,
+        };
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{prelude::*, targeting::rebuild_enemy_grid};
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicDirector>().add_systems(
+            (update_intensity, switch_tracks, apply_music_volume)
+                .chain()
+                .after(rebuild_enemy_grid),
+        );
+    }
+}
+
+const CROSSFADE_SECONDS: f32 = 1.5;
+// Elites spawn and die every few seconds, so the boss track only swaps with the gameplay
+// track once the current one has played this long.
+const MIN_COMBAT_TRACK_SECONDS: f64 = 12.0;
+// How far from the player enemies count towards intensity, and how many it takes to max it out.
+const INTENSITY_RADIUS: f32 = 8.0;
+const INTENSITY_ENEMIES: f32 = 12.0;
+const INTENSITY_SMOOTHING: f32 = 0.5;
+// Share of the track volume that is held back until intensity rises.
+const INTENSITY_HEADROOM: f32 = 0.35;
+
+struct TrackProfile {
+    asset: &'static str,
+    volume: f32,
+    speed: f32,
+}
+
+// Only one music file ships with the game, so the tracks differ by tempo and level for now.
+impl MusicTrack {
+    fn profile(&self) -> TrackProfile {
+        let track = |volume, speed| TrackProfile {
+            asset: "sounds/nightmare-on-imaginationland-8040.ogg",
+            volume,
+            speed,
+        };
+        match self {
+            MusicTrack::Menu => track(0.6, 0.9),
+            MusicTrack::GamePlay => track(0.85, 1.0),
+            MusicTrack::Boss => track(1.0, 1.1),
+            MusicTrack::GameOver => track(0.4, 0.75),
+        }
+    }
+
+    fn responds_to_intensity(&self) -> bool {
+        matches!(self, MusicTrack::GamePlay | MusicTrack::Boss)
+    }
+}
+
+fn update_intensity(
+    mut director: ResMut<MusicDirector>,
+    state: Res<State<GameState>>,
+    grid: Res<EnemyGrid>,
+    player: Query<&Transform, With<Player>>,
+    time: Res<Time>,
+) {
+    let target = match (&state.0, player.get_single()) {
+        (GameState::GamePlay, Ok(transform)) => {
+            let nearby = grid
+                .within(transform.translation.truncate(), INTENSITY_RADIUS)
+                .count();
+            (nearby as f32 / INTENSITY_ENEMIES).min(1.0)
+        }
+        _ => 0.0,
+    };
+    let step = time.delta_seconds() / INTENSITY_SMOOTHING;
+    director.intensity += (target - director.intensity).clamp(-step, step);
+}
+
+fn desired_track(state: &GameState, elite_alive: bool) -> MusicTrack {
+    match state {
+        GameState::MainMenu => MusicTrack::Menu,
+        GameState::GamePlay if elite_alive => MusicTrack::Boss,
+        GameState::GamePlay => MusicTrack::GamePlay,
+        GameState::GameOver => MusicTrack::GameOver,
+    }
+}
+
+fn switch_tracks(
+    mut director: ResMut<MusicDirector>,
+    state: Res<State<GameState>>,
+    elites: Query<(), With<Elite>>,
+    audio: Res<Audio>,
+    assets: Res<AssetServer>,
+    sinks: Res<Assets<AudioSink>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds_f64();
+    let track = desired_track(&state.0, !elites.is_empty());
+    if let Some(current) = &director.current {
+        let combat_swap = current.track.responds_to_intensity() && track.responds_to_intensity();
+        if current.track == track
+            || (combat_swap && now - current.started < MIN_COMBAT_TRACK_SECONDS)
+        {
+            return;
+        }
+    }
+
+    let profile = track.profile();
+    let sink = audio.play_with_settings(
+        assets.load(profile.asset),
+        PlaybackSettings {
+            repeat: true,
+            volume: 0.0,
+            speed: profile.speed,
+        },
+    );
+    let voice = MusicVoice {
+        track,
+        sink: sinks.get_handle(sink),
+        fade: 0.0,
+        started: now,
+    };
+    if let Some(previous) = director.current.replace(voice) {
+        director.fading_out.push(previous);
+    }
+}
+
+fn apply_music_volume(
+    mut director: ResMut<MusicDirector>,
+    settings: Res<UserSettings>,
+    sinks: Res<Assets<AudioSink>>,
+    time: Res<Time>,
+) {
+    let step = time.delta_seconds() / CROSSFADE_SECONDS;
+    let intensity = director.intensity;
+    let volume = |voice: &MusicVoice| {
+        let level = if voice.track.responds_to_intensity() {
+            1.0 - INTENSITY_HEADROOM * (1.0 - intensity)
+        } else {
+            1.0
+        };
+        voice.track.profile().volume * level * voice.fade * settings.music()
+    };
+
+    let director = &mut *director;
+    if let Some(voice) = &mut director.current {
+        voice.fade = (voice.fade + step).min(1.0);
+        if let Some(sink) = sinks.get(&voice.sink) {
+            sink.set_volume(volume(voice));
+        }
+    }
+    director.fading_out.retain_mut(|voice| {
+        voice.fade = (voice.fade - step).max(0.0);
+        let Some(sink) = sinks.get(&voice.sink) else {
+            return true;
+        };
+        if voice.fade <= 0.0 {
+            sink.stop();
+            return false;
+        }
+        sink.set_volume(volume(voice));
+        true
+    });
+}
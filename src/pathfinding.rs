@@ -0,0 +1,299 @@
+use crate::{arena::ArenaLayout, enemy::enemy_movement, prelude::*};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowField>().add_system(
+            update_flow_field
+                .before(enemy_movement)
+                .in_set(OnUpdate(GameState::GamePlay)),
+        );
+    }
+}
+
+const STRAIGHT_COST: u32 = 2;
+const DIAGONAL_COST: u32 = 3;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+impl FlowField {
+    pub fn compute(arena: &Arena, target: IVec2) -> FlowField {
+        let mut field = FlowField {
+            target: Some(target),
+            width: arena.width,
+            height: arena.height,
+            distance: vec![u32::MAX; (arena.width * arena.height) as usize],
+        };
+
+        if !arena.is_walkable(target) {
+            return field;
+        }
+
+        let mut open = BinaryHeap::new();
+        let start = field.index(target);
+        field.distance[start] = 0;
+        open.push(Reverse((0, target.x, target.y)));
+
+        while let Some(Reverse((distance, x, y))) = open.pop() {
+            let tile = IVec2::new(x, y);
+            if distance > field.distance[field.index(tile)] {
+                continue;
+            }
+
+            for (next, cost) in walkable_neighbours(arena, tile) {
+                let next_distance = distance + cost;
+                let index = field.index(next);
+                if next_distance < field.distance[index] {
+                    field.distance[index] = next_distance;
+                    open.push(Reverse((next_distance, next.x, next.y)));
+                }
+            }
+        }
+
+        field
+    }
+
+    pub fn distance(&self, tile: IVec2) -> Option<u32> {
+        if tile.x < 0 || tile.y < 0 || tile.x >= self.width || tile.y >= self.height {
+            return None;
+        }
+        match self.distance[self.index(tile)] {
+            u32::MAX => None,
+            distance => Some(distance),
+        }
+    }
+
+    pub fn next_step(&self, arena: &Arena, tile: IVec2) -> Option<IVec2> {
+        let current = self.distance(tile)?;
+        walkable_neighbours(arena, tile)
+            .filter_map(|(next, _)| self.distance(next).map(|distance| (next, distance)))
+            .filter(|(_, distance)| *distance < current)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(next, _)| next)
+    }
+
+    pub fn direction(&self, arena: &Arena, position: Vec2) -> Option<Vec2> {
+        let tile = arena.world_to_tile(position);
+        if Some(tile) == self.target {
+            return None;
+        }
+        let next = self.next_step(arena, tile)?;
+        Some((arena.tile_to_world(next) - position).normalize_or_zero())
+    }
+
+    fn index(&self, tile: IVec2) -> usize {
+        (tile.y * self.width + tile.x) as usize
+    }
+}
+
+fn walkable_neighbours(arena: &Arena, tile: IVec2) -> impl Iterator<Item = (IVec2, u32)> + '_ {
+    NEIGHBOURS.iter().filter_map(move |offset| {
+        let next = tile + *offset;
+        if !arena.is_walkable(next) {
+            return None;
+        }
+        if offset.x != 0 && offset.y != 0 {
+            let corner_x = arena.is_walkable(tile + IVec2::new(offset.x, 0));
+            let corner_y = arena.is_walkable(tile + IVec2::new(0, offset.y));
+            if !(corner_x && corner_y) {
+                return None;
+            }
+            return Some((next, DIAGONAL_COST));
+        }
+        Some((next, STRAIGHT_COST))
+    })
+}
+
+fn update_flow_field(
+    mut flow_field: ResMut<FlowField>,
+    player: Query<&Transform, With<Player>>,
+    arena: ArenaLayout,
+) {
+    let (Some(arena), Ok(player)) = (arena.get(), player.get_single()) else {
+        return;
+    };
+
+    let target = arena.world_to_tile(player.translation.truncate());
+    let stale = flow_field.width != arena.width || flow_field.height != arena.height;
+
+    // Moving the target by one tile shifts the distance of nearly every tile, so re-seeding
+    // from the previous field would visit the same tiles as a full Dijkstra pass.
+    if stale || flow_field.target != Some(target) {
+        *flow_field = FlowField::compute(arena, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arena(layout: &str) -> Arena {
+        let layout = layout.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        Arena::parse(&layout).unwrap()
+    }
+
+    fn walk(arena: &Arena, field: &FlowField, from: IVec2) -> Vec<IVec2> {
+        let mut path = vec![from];
+        let mut tile = from;
+        while let Some(next) = field.next_step(arena, tile) {
+            path.push(next);
+            tile = next;
+        }
+        path
+    }
+
+    #[test]
+    fn open_room_walks_straight_to_target() {
+        let arena = arena(
+            "
+            .....
+            .....
+            .....
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(4, 1));
+        let path = walk(&arena, &field, IVec2::new(0, 1));
+
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.last(), Some(&IVec2::new(4, 1)));
+        assert!(path.iter().all(|tile| tile.y == 1));
+    }
+
+    #[test]
+    fn routes_around_wall() {
+        let arena = arena(
+            "
+            .....
+            ..#..
+            ..#..
+            ..#..
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(4, 3));
+        let path = walk(&arena, &field, IVec2::new(0, 3));
+
+        assert_eq!(path.last(), Some(&IVec2::new(4, 3)));
+        assert!(path.iter().all(|tile| arena.is_walkable(*tile)));
+        assert!(path.contains(&IVec2::new(2, 0)));
+    }
+
+    #[test]
+    fn does_not_cut_corners() {
+        let arena = arena(
+            "
+            .#
+            ..
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(1, 1));
+
+        assert_eq!(
+            field.next_step(&arena, IVec2::new(0, 0)),
+            Some(IVec2::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn diagonal_is_cheaper_than_two_straight_steps() {
+        let arena = arena(
+            "
+            ..
+            ..
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(1, 1));
+
+        assert_eq!(field.distance(IVec2::new(0, 0)), Some(DIAGONAL_COST));
+        assert_eq!(
+            field.next_step(&arena, IVec2::new(0, 0)),
+            Some(IVec2::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn sealed_room_is_unreachable() {
+        let arena = arena(
+            "
+            ..#..
+            ..#..
+            ..#..
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(0, 1));
+
+        assert_eq!(field.distance(IVec2::new(4, 1)), None);
+        assert_eq!(field.next_step(&arena, IVec2::new(4, 1)), None);
+    }
+
+    #[test]
+    fn obstacles_and_markers() {
+        let arena = arena(
+            "
+            S.o.@
+            ..o..
+            P....
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(4, 0));
+        let path = walk(&arena, &field, IVec2::new(0, 0));
+
+        assert_eq!(path.last(), Some(&IVec2::new(4, 0)));
+        assert!(path.contains(&IVec2::new(2, 2)));
+    }
+
+    #[test]
+    fn target_inside_wall_has_no_field() {
+        let arena = arena(
+            "
+            ...
+            .#.
+            ...
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(1, 1));
+
+        assert!(field.distance.iter().all(|distance| *distance == u32::MAX));
+    }
+
+    #[test]
+    fn direction_points_toward_next_tile() {
+        let arena = arena(
+            "
+            ...
+            ...
+            ...
+            ",
+        );
+        let field = FlowField::compute(&arena, IVec2::new(2, 1));
+        let position = arena.tile_to_world(IVec2::new(0, 1));
+        let direction = field.direction(&arena, position).unwrap();
+
+        assert!((direction - Vec2::X).length() < 1e-5);
+        assert_eq!(
+            field.direction(&arena, arena.tile_to_world(IVec2::new(2, 1))),
+            None
+        );
+    }
+
+    #[test]
+    fn default_arena_spawn_points_reach_player_start() {
+        let arena = Arena::parse(include_str!("../assets/arenas/default.arena")).unwrap();
+        let start = arena.world_to_tile(arena.player_start());
+        let field = FlowField::compute(&arena, start);
+
+        for spawn in arena.cells(ArenaTile::SpawnPoint) {
+            assert_eq!(walk(&arena, &field, spawn).last(), Some(&start));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    menu::{menu_button, menu_text},
+    prelude::*,
+};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<PauseState>()
+            .configure_set(OnUpdate(GameState::GamePlay).run_if(in_state(PauseState::Running)))
+            .add_system(toggle_pause.run_if(in_state(GameState::GamePlay)))
+            .add_system(resume_on_exit.in_schedule(OnExit(GameState::GamePlay)))
+            .add_system(pause_physics.in_schedule(OnEnter(PauseState::Paused)))
+            .add_system(resume_physics.in_schedule(OnExit(PauseState::Paused)))
+            .add_system(spawn_pause_ui.in_schedule(OnEnter(PauseState::Paused)))
+            .add_system(despawn_pause_ui.in_schedule(OnExit(PauseState::Paused)))
+            .add_system(pause_menu_actions.in_set(OnUpdate(PauseState::Paused)));
+    }
+}
+
+fn toggle_pause(
+    actions: Res<ActionState>,
+    pause_state: Res<State<PauseState>>,
+    mut next_state: ResMut<NextState<PauseState>>,
+) {
+    if !actions.just_pressed(Action::Pause) {
+        return;
+    }
+    match pause_state.0 {
+        PauseState::Running => next_state.set(PauseState::Paused),
+        PauseState::Paused => next_state.set(PauseState::Running),
+    }
+}
+
+fn resume_on_exit(mut next_state: ResMut<NextState<PauseState>>) {
+    next_state.set(PauseState::Running);
+}
+
+fn pause_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = false;
+}
+
+fn resume_physics(
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut settings: ResMut<SettingsShown>,
+    mut controls: ResMut<ControlsShown>,
+) {
+    rapier_config.physics_pipeline_active = true;
+    settings.0 = false;
+    controls.0 = false;
+}
+
+fn pause_menu_actions(
+    mut activated: EventReader<MenuActivated>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+) {
+    for MenuActivated(action) in activated.iter() {
+        match action {
+            MenuAction::Resume => pause_state.set(PauseState::Running),
+            MenuAction::AbandonRun => {
+                pause_state.set(PauseState::Running);
+                game_state.set(GameState::GameOver);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn despawn_pause_ui(mut commands: Commands, ui: Query<Entity, With<PauseUI>>) {
+    for ui in &ui {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+fn spawn_pause_ui(mut commands: Commands, localizer: Localizer, theme: Res<MenuTheme>) {
+    let menu_parent = (
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        PauseUI,
+        Name::new("Pause UI"),
+    );
+
+    let menu_box = NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(50.0), Val::Percent(60.0)),
+            position_type: PositionType::Relative,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceAround,
+            ..default()
+        },
+        background_color: Color::DARK_GRAY.into(),
+        ..default()
+    };
+
+    let title_text = localizer.localized("pause.title", 64.0, Color::rgb(0.9, 0.9, 0.9));
+
+    let button_size = Size::new(Val::Percent(70.0), Val::Percent(15.0));
+
+    commands.spawn(menu_parent).with_children(|commands| {
+        commands.spawn(menu_box).with_children(|commands| {
+            commands.spawn(title_text);
+            for (action, text) in [
+                (MenuAction::Resume, "pause.resume"),
+                (MenuAction::OpenSettings, "menu.settings"),
+                (MenuAction::AbandonRun, "pause.abandon"),
+            ] {
+                commands
+                    .spawn(menu_button(&theme, action, MENU_LAYER, button_size))
+                    .with_children(|commands| {
+                        commands.spawn(menu_text(&theme, &localizer, text, 40.0));
+                    });
+            }
+        });
+    });
+}
@@ -1,13 +1,16 @@
-use crate::{attacks::spawn_blade, prelude::*};
+use crate::{arena::ArenaLayout, attacks::spawn_blade, prelude::*};
+use std::time::Duration;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(spawn_player.in_schedule(OnEnter(GameState::GamePlay)))
+        app.add_event::<DashChanged>()
+            .add_system(spawn_player.in_schedule(OnEnter(GameState::GamePlay)))
             .add_systems(
                 (
-                    player_movement.after(spawn_player),
+                    player_dash.after(spawn_player),
+                    player_movement.after(player_dash),
                     player_game_over,
                     player_drink_potion.after(player_movement),
                 )
@@ -18,87 +21,162 @@ impl Plugin for PlayerPlugin {
 
 pub fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-    animaitons: Res<Animations>,
+    asset_server: GameAssets,
+    animaitons: Option<Res<Animations>>,
+    arena: ArenaLayout,
 ) {
-    let _window: &Window = window_query.get_single().unwrap();
+    let start = arena
+        .get()
+        .map(|arena| arena.player_start())
+        .unwrap_or(Vec2::ZERO);
     let blade = spawn_blade(&mut commands, &asset_server);
+    let transform = Transform::from_translation(start.extend(2.0));
 
+    let mut player = commands.spawn((
+        SpatialBundle::from_transform(transform),
+        Player {
+            health: 100.0,
+            max_health: 100.0,
+            speed: 5.0,
+            damage: 5.0,
+            facing: Facing::Right,
+            state: PlayerState::Idle,
+            gold: 0,
+            xp: 0,
+        },
+        Dash::default(),
+        ActivePowerUps::default(),
+        Name::new("Player"),
+        Collider::capsule(Vec2::new(0.0, 0.55), Vec2::new(0.0, -0.50), 0.5),
+        RigidBody::KinematicPositionBased,
+        KinematicCharacterController {
+            filter_flags: QueryFilterFlags::EXCLUDE_DYNAMIC | QueryFilterFlags::EXCLUDE_SENSORS,
+            ..default()
+        },
+        GamePlayEntity,
+    ));
+    player.add_child(blade);
+
+    // Headless runs have no animations to show.
+    let Some(animaitons) = animaitons else {
+        return;
+    };
     let Some((texture_atlas, animation)) = animaitons.get(Animation::PlayerIdle) else {error!("Failed to find animation: Idle"); return;};
-    commands
-        .spawn((
-            SpriteSheetBundle {
-                texture_atlas,
-                transform: Transform::from_xyz(0.0, 0.0, 2.0),
-                sprite: TextureAtlasSprite {
-                    index: 0,
-                    custom_size: Some(Vec2::new(80.0 * PIXEL_TO_WORLD, 80.0 * PIXEL_TO_WORLD)),
-                    ..Default::default()
-                },
+    player.insert((
+        SpriteSheetBundle {
+            texture_atlas,
+            transform,
+            sprite: TextureAtlasSprite {
+                index: 0,
+                custom_size: Some(Vec2::new(80.0 * PIXEL_TO_WORLD, 80.0 * PIXEL_TO_WORLD)),
                 ..Default::default()
             },
-            Player {
-                health: 100.0,
-                max_health: 100.0,
-                speed: 5.0,
-                damage: 5.0,
-                facing: Facing::Right,
-                state: PlayerState::Idle,
+            ..Default::default()
+        },
+        animation,
+        FrameTime(0.0),
+    ));
+}
+
+const DASH_DURATION: f32 = 0.18;
+const MIN_DASH_COOLDOWN: f32 = 0.2;
+
+impl Dash {
+    pub fn effective_cooldown(&self) -> f32 {
+        (self.cooldown * (1.0 - self.cooldown_reduction)).max(MIN_DASH_COOLDOWN)
+    }
+}
+
+fn player_dash(
+    mut commands: Commands,
+    mut player: Query<(Entity, &Player, &mut Dash)>,
+    actions: Res<ActionState>,
+    time: Res<Time>,
+    mut changed: EventWriter<DashChanged>,
+    mut last_charges: Local<(u32, u32)>,
+) {
+    let Ok((entity, player, mut dash)) = player.get_single_mut() else {
+        return;
+    };
+
+    let recharging = dash.charges < dash.max_charges;
+    if recharging {
+        let cooldown = Duration::from_secs_f32(dash.effective_cooldown());
+        dash.recharge.set_duration(cooldown);
+        dash.recharge.tick(time.delta());
+        if dash.recharge.finished() {
+            dash.charges += 1;
+            dash.recharge.reset();
+        }
+    }
+
+    if let Some((_, timer)) = dash.active.as_mut() {
+        timer.tick(time.delta());
+        if timer.finished() {
+            dash.active = None;
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+
+    if actions.just_pressed(Action::Dash) && dash.active.is_none() && dash.charges > 0 {
+        let direction = if actions.movement == Vec2::ZERO {
+            player.facing.direction()
+        } else {
+            actions.movement.normalize()
+        };
+        dash.charges -= 1;
+        dash.active = Some((
+            direction,
+            Timer::from_seconds(DASH_DURATION, TimerMode::Once),
+        ));
+        commands.entity(entity).insert(Invulnerable);
+    }
+
+    // Power-ups change the charges from elsewhere, so compare against what was last sent.
+    let charges = (dash.charges, dash.max_charges);
+    if recharging || dash.is_added() || charges != *last_charges {
+        *last_charges = charges;
+        changed.send(DashChanged {
+            charges: dash.charges,
+            max_charges: dash.max_charges,
+            recharge: if dash.charges == dash.max_charges {
+                1.0
+            } else {
+                dash.recharge.percent()
             },
-            Name::new("Player"),
-            Collider::capsule(Vec2::new(0.0, 0.55), Vec2::new(0.0, -0.50), 0.5),
-            GamePlayEntity,
-            animation,
-            FrameTime(0.0),
-        ))
-        .add_child(blade);
+        });
+    }
 }
 
 pub fn player_movement(
-    mut player: Query<(&mut Transform, &mut Player)>,
-    input: Res<Input<KeyCode>>,
+    mut player: Query<(&mut KinematicCharacterController, &mut Player, &Dash)>,
+    actions: Res<ActionState>,
     time: Res<Time>,
 ) {
-    let (mut transform, mut player) = player.single_mut();
-    if input.pressed(KeyCode::W) {
-        transform.translation.y += time.delta_seconds() * player.speed;
-        player.facing = Facing::Up;
-        player.state = PlayerState::Moving;
-    } else if input.pressed(KeyCode::S) {
-        transform.translation.y -= time.delta_seconds() * player.speed;
-        player.facing = Facing::Down;
-        player.state = PlayerState::Moving;
-    } else if input.pressed(KeyCode::A) {
-        transform.translation.x -= time.delta_seconds() * player.speed;
-        player.facing = Facing::Left;
-        player.state = PlayerState::Moving;
-    } else if input.pressed(KeyCode::D) {
-        transform.translation.x += time.delta_seconds() * player.speed;
-        player.facing = Facing::Right;
-        player.state = PlayerState::Moving;
-    } else {
-        player.state = PlayerState::Idle
+    let (mut controller, mut player, dash) = player.single_mut();
+    match Facing::from_direction(actions.movement) {
+        Some(facing) => {
+            player.facing = facing;
+            player.state = PlayerState::Moving;
+        }
+        None => player.state = PlayerState::Idle,
+    }
+    let velocity = match dash.active {
+        Some((direction, _)) => direction * dash.speed,
+        None => actions.movement * player.speed,
     };
+    controller.translation = Some(velocity * time.delta_seconds());
 }
 
 fn player_game_over(
     player: Query<&Player>,
     mut game_state: ResMut<NextState<GameState>>,
-    audio: Res<Audio>,
-    assets: Res<AssetServer>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
     let player = player.single();
 
     if player.health <= 0.0 {
-        audio.play_with_settings(
-            assets.load("sounds/you_lose.ogg"),
-            PlaybackSettings {
-                repeat: false,
-                volume: 0.9,
-                speed: 1.0,
-            },
-        );
+        sfx.send(PlaySfx::global(Sfx::GameOver));
         game_state.set(GameState::GameOver);
     }
 }
@@ -106,8 +184,7 @@ fn player_game_over(
 fn player_drink_potion(
     potion: Query<(&Collider, &GlobalTransform, &Potion)>,
     mut player: Query<&mut Player>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
+    mut sfx: EventWriter<PlaySfx>,
     rapier_context: Res<RapierContext>,
 ) {
     for (collider, transform, _potion) in &potion {
@@ -119,9 +196,7 @@ fn player_drink_potion(
             |entity| {
                 if let Ok(mut player) = player.get_mut(entity) {
                     player.health += 10.0;
-
-                    let sound_effect = asset_server.load("sounds/slime_000.ogg");
-                    audio.play(sound_effect);
+                    sfx.send(PlaySfx::global(Sfx::Potion));
                 }
                 true
             },
@@ -0,0 +1,163 @@
+use crate::prelude::*;
+use bevy::{
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin},
+};
+
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(Material2dPlugin::<PostProcessMaterial>::default())
+            .init_resource::<PostProcessSettings>()
+            .add_systems(
+                (low_health_vignette, player_hit_flash, biome_grading)
+                    .in_set(OnUpdate(GameState::GamePlay)),
+            )
+            .add_system(reset_post_process.in_schedule(OnEnter(GameState::MainMenu)))
+            .add_system(sync_post_process);
+    }
+}
+
+pub use uniform::PostProcessUniform;
+
+// The ShaderType derive emits per-field layout checks that rustc flags as unused.
+#[allow(dead_code)]
+mod uniform {
+    use bevy::{math::Vec4, render::render_resource::ShaderType};
+
+    #[derive(ShaderType, Clone, Copy, Debug, Default)]
+    pub struct PostProcessUniform {
+        pub flash_color: Vec4,
+        pub grade_tint: Vec4,
+        pub vignette: f32,
+        pub flash: f32,
+        pub crt: f32,
+        pub saturation: f32,
+        pub contrast: f32,
+        pub brightness: f32,
+        pub time: f32,
+        pub resolution_y: f32,
+    }
+}
+
+#[derive(AsBindGroup, TypeUuid, Clone, Debug)]
+#[uuid = "1d9a3c5e-7f2b-4e8a-b6c4-0a5d8e3f7b21"]
+pub struct PostProcessMaterial {
+    #[uniform(0)]
+    pub settings: PostProcessUniform,
+    #[texture(1)]
+    #[sampler(2)]
+    pub source: Handle<Image>,
+}
+
+impl PostProcessMaterial {
+    pub fn new(source: Handle<Image>) -> PostProcessMaterial {
+        PostProcessMaterial {
+            settings: PostProcessUniform {
+                saturation: 1.0,
+                contrast: 1.0,
+                grade_tint: Vec4::ONE,
+                ..default()
+            },
+            source,
+        }
+    }
+}
+
+impl Material2d for PostProcessMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/post_process.wgsl".into()
+    }
+}
+
+impl ColorGrading {
+    pub fn lerp(&self, other: &ColorGrading, t: f32) -> ColorGrading {
+        let tint = Vec4::from(self.tint.as_rgba_f32())
+            .lerp(Vec4::from(other.tint.as_rgba_f32()), t)
+            .to_array();
+        ColorGrading {
+            tint: Color::rgba(tint[0], tint[1], tint[2], tint[3]),
+            saturation: self.saturation + (other.saturation - self.saturation) * t,
+            contrast: self.contrast + (other.contrast - self.contrast) * t,
+            brightness: self.brightness + (other.brightness - self.brightness) * t,
+        }
+    }
+}
+
+fn low_health_vignette(player: Query<&Player>, mut settings: ResMut<PostProcessSettings>) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let ratio = (player.health / player.max_health).clamp(0.0, 1.0);
+    settings.vignette = ((0.35 - ratio) / 0.35).clamp(0.0, 1.0);
+}
+
+fn player_hit_flash(
+    player: Query<&Player>,
+    mut last_health: Local<Option<f32>>,
+    mut settings: ResMut<PostProcessSettings>,
+    time: Res<Time>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    if last_health.is_some_and(|last| player.health < last) {
+        settings.flash = 1.0;
+        settings.flash_color = Color::rgba(1.0, 0.1, 0.1, 0.4);
+    }
+    *last_health = Some(player.health);
+
+    settings.flash = (settings.flash - settings.flash_decay * time.delta_seconds()).max(0.0);
+}
+
+fn biome_grading(
+    camera: Query<&Transform, With<MainCamera>>,
+    chunks: Res<BackgroundChunks>,
+    mut settings: ResMut<PostProcessSettings>,
+    time: Res<Time>,
+) {
+    let Ok(camera) = camera.get_single() else {
+        return;
+    };
+
+    let biome = chunks.variant_index(chunks.chunk_at(camera.translation.truncate()));
+    let Some(target) = settings.biome_grading.get(biome).copied() else {
+        return;
+    };
+    let blend = 1.0 - (-2.0 * time.delta_seconds()).exp();
+    settings.grading = settings.grading.lerp(&target, blend);
+}
+
+fn reset_post_process(mut settings: ResMut<PostProcessSettings>) {
+    settings.vignette = 0.0;
+    settings.flash = 0.0;
+    settings.grading = ColorGrading::default();
+}
+
+fn sync_post_process(
+    settings: Res<PostProcessSettings>,
+    render_settings: Res<RenderSettings>,
+    quad: Query<&Handle<PostProcessMaterial>, With<BaseRender>>,
+    mut materials: ResMut<Assets<PostProcessMaterial>>,
+    time: Res<Time>,
+) {
+    for handle in &quad {
+        let Some(material) = materials.get_mut(handle) else {
+            continue;
+        };
+        material.settings = PostProcessUniform {
+            flash_color: Vec4::from(settings.flash_color.as_rgba_f32()),
+            grade_tint: Vec4::from(settings.grading.tint.as_rgba_f32()),
+            vignette: settings.vignette,
+            flash: settings.flash,
+            crt: settings.crt,
+            saturation: settings.grading.saturation,
+            contrast: settings.grading.contrast,
+            brightness: settings.grading.brightness,
+            time: time.elapsed_seconds_wrapped(),
+            resolution_y: render_settings.height as f32,
+        };
+    }
+}
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{arena::ArenaLayout, prelude::*};
 
 pub struct PotionsPlugin;
 
@@ -22,9 +22,11 @@ pub fn spawn_potions(
     mut spawn_manager: ResMut<PotionManager>,
     mut global_rng: ResMut<GlobalRng>,
     time: Res<Time>,
-    assets: Res<AssetServer>,
+    assets: GameAssets,
+    arena: ArenaLayout,
 ) {
     let player_transform = player.single();
+    let arena = arena.get();
 
     spawn_manager.potion_time.tick(time.delta());
 
@@ -53,31 +55,54 @@ pub fn spawn_potions(
             potion.health *= 1.3_f32.powf(wave_buf as f32);
 
             target_translation += player_transform.translation.truncate().extend(0.0);
-            commands.spawn((
-                SpriteBundle {
-                    texture: assets.load(&wave.to_spawn_potion.asset),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(32.0 * PIXEL_TO_WORLD, 32.0 * PIXEL_TO_WORLD)),
-                        ..default()
-                    },
-                    transform: Transform::from_translation(target_translation),
-                    ..default()
-                },
+            if let Some(arena) = arena {
+                target_translation = arena
+                    .pickup_position(target_translation.truncate())
+                    .extend(target_translation.z);
+            }
+            spawn_potion_entity(
+                &mut commands,
+                &assets,
+                &mut global_rng,
                 potion,
-                Name::new("Potion"),
-                RngComponent::from(&mut global_rng),
-                RigidBody::Dynamic,
-                LockedAxes::ROTATION_LOCKED_Z,
-                Damping {
-                    linear_damping: 100.0,
-                    angular_damping: 1.0,
-                },
-                Collider::ball(0.9),
-                GamePlayEntity,
-            ));
+                target_translation,
+            );
         }
     }
 }
+
+pub fn spawn_potion_entity(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    global_rng: &mut GlobalRng,
+    potion: Potion,
+    translation: Vec3,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: assets.load(&potion.asset),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(32.0 * PIXEL_TO_WORLD, 32.0 * PIXEL_TO_WORLD)),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            potion,
+            Name::new("Potion"),
+            RngComponent::from(global_rng),
+            RigidBody::Dynamic,
+            LockedAxes::ROTATION_LOCKED_Z,
+            Damping {
+                linear_damping: 100.0,
+                angular_damping: 1.0,
+            },
+            Collider::ball(0.9),
+            GamePlayEntity,
+        ))
+        .id()
+}
 /*
 pub fn tick_potion_spawn_timer(mut potion_spawn_timer: ResMut<PotionSpawnTimer>, time: Res<Time>) {
     potion_spawn_timer.timer.tick(time.delta());
@@ -87,7 +112,7 @@ pub fn spawn_potions_over_time(
     mut commands: Commands,
     player: Query<&Transform, With<Player>>,
     mut global_rng: ResMut<GlobalRng>,
-    asset_server: Res<AssetServer>,
+    asset_server: GameAssets,
     potion_spawn_timer: Res<PotionSpawnTimer>,
 ) {
     if potion_spawn_timer.timer.finished() {
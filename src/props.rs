@@ -0,0 +1,162 @@
+use crate::{
+    arena::ArenaLayout,
+    attacks::{blade_attack, dagger_hits},
+    loot::spawn_loot,
+    prelude::*,
+};
+
+pub struct PropsPlugin;
+
+impl Plugin for PropsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PropSpawner>().add_systems(
+            (
+                spawn_props,
+                prop_break_check.after(blade_attack).after(dagger_hits),
+            )
+                .in_set(OnUpdate(GameState::GamePlay)),
+        );
+    }
+}
+
+const PROP_KINDS: [PropKind; 3] = [PropKind::Crate, PropKind::Barrel, PropKind::Urn];
+
+impl PropKind {
+    pub fn health(&self) -> f32 {
+        match self {
+            PropKind::Crate => 10.0,
+            PropKind::Barrel => 15.0,
+            PropKind::Urn => 5.0,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            PropKind::Crate => Vec2::new(1.4, 1.4),
+            PropKind::Barrel => Vec2::new(1.2, 1.5),
+            PropKind::Urn => Vec2::new(0.9, 1.2),
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PropKind::Crate => Color::rgb(0.55, 0.38, 0.2),
+            PropKind::Barrel => Color::rgb(0.4, 0.25, 0.12),
+            PropKind::Urn => Color::rgb(0.75, 0.55, 0.4),
+        }
+    }
+
+    pub fn drop_table(&self) -> DropTable {
+        match self {
+            PropKind::Crate => DropTable {
+                nothing: 2,
+                entries: vec![(Loot::Coins(5), 4), (Loot::Xp(2), 3), (Loot::Potion, 1)],
+            },
+            PropKind::Barrel => DropTable {
+                nothing: 1,
+                entries: vec![
+                    (Loot::Potion, 3),
+                    (Loot::Coins(3), 2),
+                    (Loot::PowerUp(PowerUp::Haste), 1),
+                    (Loot::PowerUp(PowerUp::Swiftness), 1),
+                ],
+            },
+            PropKind::Urn => DropTable {
+                nothing: 3,
+                entries: vec![
+                    (Loot::Coins(10), 3),
+                    (Loot::Xp(5), 2),
+                    (Loot::PowerUp(PowerUp::Might), 1),
+                    (Loot::PowerUp(PowerUp::Focus), 1),
+                    (Loot::PowerUp(PowerUp::Daggers), 1),
+                ],
+            },
+        }
+    }
+}
+
+fn spawn_props(
+    mut commands: Commands,
+    mut spawner: ResMut<PropSpawner>,
+    props: Query<(), With<Prop>>,
+    player: Query<&Transform, With<Player>>,
+    arena: ArenaLayout,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player.get_single() else {
+        return;
+    };
+
+    spawner.timer.tick(time.delta());
+    if !spawner.timer.just_finished() || props.iter().count() >= spawner.max_props {
+        return;
+    }
+
+    let mut position = player_transform.translation.truncate()
+        + Vec2::new(global_rng.f32_normalized(), global_rng.f32_normalized()) * 25.0;
+    if let Some(arena) = arena.get() {
+        position = arena.nearest_floor(position);
+    }
+
+    let kind = PROP_KINDS[global_rng.usize(0..PROP_KINDS.len())];
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: kind.color(),
+                custom_size: Some(kind.size()),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(4.0)),
+            ..default()
+        },
+        Prop {
+            kind,
+            health: kind.health(),
+        },
+        Name::new("Prop"),
+        RigidBody::Fixed,
+        Collider::cuboid(kind.size().x / 2.0, kind.size().y / 2.0),
+        GamePlayEntity,
+    ));
+}
+
+/// Shared by every player weapon. Returns whether `entity` was a prop.
+pub fn hit_prop(props: &mut Query<&mut Prop>, entity: Entity, damage: f32) -> bool {
+    let Ok(mut prop) = props.get_mut(entity) else {
+        return false;
+    };
+    prop.health -= damage;
+    true
+}
+
+fn prop_break_check(
+    mut commands: Commands,
+    props: Query<(Entity, &Transform, &Prop)>,
+    potion_manager: Res<PotionManager>,
+    mut global_rng: ResMut<GlobalRng>,
+    assets: GameAssets,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    for (entity, transform, prop) in &props {
+        if prop.health > 0.0 {
+            continue;
+        }
+
+        sfx.send(PlaySfx::at(Sfx::PropBreak, transform.translation));
+
+        if let Some(loot) = prop.kind.drop_table().roll(&mut *global_rng) {
+            spawn_loot(
+                &mut commands,
+                &assets,
+                &mut global_rng,
+                &potion_manager,
+                loot,
+                transform.translation,
+            );
+        }
+
+        commands.entity(entity).despawn_recursive();
+    }
+}
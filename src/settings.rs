@@ -0,0 +1,377 @@
+use crate::{
+    localization::LANGUAGES,
+    menu::{menu_button, menu_text},
+    prelude::*,
+};
+use bevy::window::{PresentMode, WindowMode};
+use serde::de::DeserializeOwned;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UserSettings::load())
+            .insert_resource(SettingsShown(false))
+            .add_startup_system(spawn_settings_ui)
+            .add_systems((settings_menu_actions, show_settings_ui, sync_settings_text))
+            .add_systems((apply_window_settings, apply_camera_settings, save_settings));
+    }
+}
+
+const VOLUME_STEP: f32 = 0.1;
+
+fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join("bevyquest"))
+}
+
+pub fn config_path(file: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(file))
+}
+
+pub fn load_config<T: DeserializeOwned + Default>(file: &str) -> T {
+    match config_path(file) {
+        Some(path) => load_config_from(&path),
+        None => T::default(),
+    }
+}
+
+fn load_config_from<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let Ok(source) = fs::read_to_string(path) else {
+        return T::default();
+    };
+    ron::from_str(&source).unwrap_or_else(|error| {
+        warn!("Ignoring invalid config file {}: {error}", path.display());
+        T::default()
+    })
+}
+
+pub fn save_config<T: Serialize>(file: &str, value: &T) {
+    let Some(path) = config_path(file) else {
+        return;
+    };
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|source| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+            }
+            fs::write(&path, source).map_err(|error| error.to_string())
+        });
+    if let Err(error) = result {
+        warn!("Failed to save {}: {error}", path.display());
+    }
+}
+
+impl UserSettings {
+    pub fn load() -> UserSettings {
+        load_config("settings.ron")
+    }
+
+    pub fn save(&self) {
+        save_config("settings.ron", self);
+    }
+
+    fn step(&mut self, option: SettingsOption) {
+        let next_volume = |volume: f32| {
+            let next = ((volume.max(0.0) + VOLUME_STEP) * 10.0).round() / 10.0;
+            if next > 1.0 {
+                0.0
+            } else {
+                next
+            }
+        };
+
+        match option {
+            SettingsOption::MasterVolume => self.master_volume = next_volume(self.master_volume),
+            SettingsOption::MusicVolume => self.music_volume = next_volume(self.music_volume),
+            SettingsOption::SfxVolume => self.sfx_volume = next_volume(self.sfx_volume),
+            SettingsOption::UiVolume => self.ui_volume = next_volume(self.ui_volume),
+            SettingsOption::WindowMode => {
+                self.window_mode = match self.window_mode {
+                    WindowModeSetting::Windowed => WindowModeSetting::Borderless,
+                    WindowModeSetting::Borderless => WindowModeSetting::Fullscreen,
+                    WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+                }
+            }
+            SettingsOption::Vsync => self.vsync = !self.vsync,
+            SettingsOption::ScreenShake => self.screen_shake = !self.screen_shake,
+            SettingsOption::DamageNumbers => self.damage_numbers = !self.damage_numbers,
+            SettingsOption::EnemyHealthBars => self.enemy_health_bars = !self.enemy_health_bars,
+            SettingsOption::AimMode => {
+                self.aim_mode = match self.aim_mode {
+                    AimMode::Facing => AimMode::Free,
+                    AimMode::Free => AimMode::Facing,
+                }
+            }
+            SettingsOption::Language => {
+                let index = LANGUAGES
+                    .iter()
+                    .position(|language| *language == self.language)
+                    .map_or(0, |index| (index + 1) % LANGUAGES.len());
+                self.language = LANGUAGES[index].to_string();
+            }
+        }
+    }
+}
+
+impl SettingsOption {
+    const ALL: [SettingsOption; 11] = [
+        SettingsOption::MasterVolume,
+        SettingsOption::MusicVolume,
+        SettingsOption::SfxVolume,
+        SettingsOption::UiVolume,
+        SettingsOption::WindowMode,
+        SettingsOption::Vsync,
+        SettingsOption::ScreenShake,
+        SettingsOption::DamageNumbers,
+        SettingsOption::EnemyHealthBars,
+        SettingsOption::AimMode,
+        SettingsOption::Language,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            SettingsOption::MasterVolume => "settings.master_volume",
+            SettingsOption::MusicVolume => "settings.music_volume",
+            SettingsOption::SfxVolume => "settings.sfx_volume",
+            SettingsOption::UiVolume => "settings.ui_volume",
+            SettingsOption::WindowMode => "settings.window_mode",
+            SettingsOption::Vsync => "settings.vsync",
+            SettingsOption::ScreenShake => "settings.screen_shake",
+            SettingsOption::DamageNumbers => "settings.damage_numbers",
+            SettingsOption::EnemyHealthBars => "settings.enemy_health_bars",
+            SettingsOption::AimMode => "settings.aim_mode",
+            SettingsOption::Language => "settings.language",
+        }
+    }
+
+    fn label(&self, settings: &UserSettings, localizer: &Localizer) -> String {
+        let on_off =
+            |value: bool| localizer.text(if value { "settings.on" } else { "settings.off" });
+        let percent = |value: f32| format!("{}%", (value * 100.0).round() as i32);
+
+        let value = match self {
+            SettingsOption::MasterVolume => percent(settings.master_volume),
+            SettingsOption::MusicVolume => percent(settings.music_volume),
+            SettingsOption::SfxVolume => percent(settings.sfx_volume),
+            SettingsOption::UiVolume => percent(settings.ui_volume),
+            SettingsOption::WindowMode => localizer.text(match settings.window_mode {
+                WindowModeSetting::Windowed => "settings.windowed",
+                WindowModeSetting::Borderless => "settings.borderless",
+                WindowModeSetting::Fullscreen => "settings.fullscreen",
+            }),
+            SettingsOption::Vsync => on_off(settings.vsync),
+            SettingsOption::ScreenShake => on_off(settings.screen_shake),
+            SettingsOption::DamageNumbers => on_off(settings.damage_numbers),
+            SettingsOption::EnemyHealthBars => on_off(settings.enemy_health_bars),
+            SettingsOption::AimMode => localizer.text(match settings.aim_mode {
+                AimMode::Facing => "settings.aim_facing",
+                AimMode::Free => "settings.aim_free",
+            }),
+            SettingsOption::Language => localizer.language_name(&settings.language),
+        };
+        format!("{}: {value}", localizer.text(self.key()))
+    }
+}
+
+fn apply_window_settings(
+    settings: Res<UserSettings>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let Ok(mut window) = window.get_single_mut() else {
+        return;
+    };
+
+    window.mode = match settings.window_mode {
+        WindowModeSetting::Windowed => WindowMode::Windowed,
+        WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen,
+        WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+    };
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+}
+
+fn apply_camera_settings(settings: Res<UserSettings>, mut controller: ResMut<CameraController>) {
+    if settings.is_changed() {
+        controller.shake_enabled = settings.screen_shake;
+    }
+}
+
+fn save_settings(settings: Res<UserSettings>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save();
+    }
+}
+
+fn settings_menu_actions(
+    mut activated: EventReader<MenuActivated>,
+    mut shown: ResMut<SettingsShown>,
+    mut settings: ResMut<UserSettings>,
+) {
+    for MenuActivated(action) in activated.iter() {
+        match action {
+            MenuAction::OpenSettings => shown.0 = true,
+            MenuAction::CloseSettings => shown.0 = false,
+            MenuAction::CycleSetting(option) => settings.step(*option),
+            _ => {}
+        }
+    }
+}
+
+fn show_settings_ui(mut ui: Query<&mut Visibility, With<SettingsUI>>, shown: Res<SettingsShown>) {
+    for mut visible in &mut ui {
+        if shown.0 {
+            *visible = Visibility::Visible;
+        } else {
+            *visible = Visibility::Hidden;
+        }
+    }
+}
+
+fn sync_settings_text(
+    options: Query<(&SettingsOption, &Children)>,
+    mut text: Query<&mut Text>,
+    settings: Res<UserSettings>,
+    localizer: Localizer,
+) {
+    if !settings.is_changed() && !localizer.is_changed() {
+        return;
+    }
+    for (option, children) in &options {
+        for child in children {
+            if let Ok(mut text) = text.get_mut(*child) {
+                let label = option.label(&settings, &localizer);
+                text.sections[0].style.font = localizer.font(&label);
+                text.sections[0].value = label;
+            }
+        }
+    }
+}
+
+fn spawn_settings_ui(
+    mut commands: Commands,
+    localizer: Localizer,
+    settings: Res<UserSettings>,
+    theme: Res<MenuTheme>,
+) {
+    let settings_parent = (
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(150),
+            ..default()
+        },
+        SettingsUI,
+        Name::new("Settings UI"),
+    );
+
+    let settings_box = NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(60.0), Val::Percent(90.0)),
+            position_type: PositionType::Relative,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceAround,
+            ..default()
+        },
+        background_color: Color::DARK_GRAY.into(),
+        ..default()
+    };
+
+    let button_size = Size::new(Val::Percent(80.0), Val::Percent(6.0));
+
+    commands.spawn(settings_parent).with_children(|commands| {
+        commands.spawn(settings_box).with_children(|commands| {
+            commands.spawn(menu_text(&theme, &localizer, "settings.title", 48.0));
+            for option in SettingsOption::ALL {
+                let action = MenuAction::CycleSetting(option);
+                commands
+                    .spawn((
+                        menu_button(&theme, action, SETTINGS_LAYER, button_size),
+                        option,
+                    ))
+                    .with_children(|commands| {
+                        let label = option.label(&settings, &localizer);
+                        let style = localizer.style(&label, 28.0, theme.text);
+                        commands.spawn(TextBundle::from_section(label, style));
+                    });
+            }
+            for (action, text) in [
+                (MenuAction::OpenControls, "settings.controls"),
+                (MenuAction::CloseSettings, "settings.back"),
+            ] {
+                commands
+                    .spawn(menu_button(&theme, action, SETTINGS_LAYER, button_size))
+                    .with_children(|commands| {
+                        commands.spawn(menu_text(&theme, &localizer, text, 32.0));
+                    });
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stepped(volume: f32) -> f32 {
+        let mut settings = UserSettings {
+            master_volume: volume,
+            ..default()
+        };
+        settings.step(SettingsOption::MasterVolume);
+        settings.master_volume
+    }
+
+    #[test]
+    fn volume_steps_wrap_from_full_to_silent() {
+        assert_eq!(stepped(0.0), 0.1);
+        assert_eq!(stepped(0.9), 1.0);
+        assert_eq!(stepped(1.0), 0.0);
+        assert_eq!(stepped(0.95), 0.0);
+        assert_eq!(stepped(-0.5), 0.1);
+        assert_eq!(stepped(3.0), 0.0);
+    }
+
+    #[test]
+    fn missing_or_corrupt_settings_fall_back_to_defaults() {
+        let dir = std::env::temp_dir().join(format!("bevyquest-settings-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let defaults = UserSettings::default();
+
+        let missing: UserSettings = load_config_from(&dir.join("missing.ron"));
+        assert_eq!(missing, defaults);
+
+        let corrupt_path = dir.join("corrupt.ron");
+        fs::write(&corrupt_path, "(master_volume: \"loud\"").unwrap();
+        let corrupt: UserSettings = load_config_from(&corrupt_path);
+        assert_eq!(corrupt, defaults);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
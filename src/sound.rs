@@ -0,0 +1,190 @@
+use crate::prelude::*;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SfxVoices>()
+            .add_event::<PlaySfx>()
+            .add_systems((menu_select_sfx, enemy_death_sfx))
+            .add_system(play_sfx.in_base_set(CoreSet::PostUpdate));
+    }
+}
+
+// Sounds within the visible area play at full volume, then fade out until
+// `AUDIBLE_SCREENS` screen half-extents away from the camera.
+const AUDIBLE_SCREENS: f32 = 2.5;
+const MIN_ATTENUATION: f32 = 0.15;
+const MAX_PAN: f32 = 0.8;
+// Ears sit at ±1 on the x axis, so an emitter at `x = pan` stays within rodio's unattenuated range.
+const EAR_GAP: f32 = 2.0;
+
+struct SfxProfile {
+    asset: &'static str,
+    category: AudioCategory,
+    volume: f32,
+    max_voices: usize,
+    // How long a voice counts against `max_voices` after it starts.
+    length: f32,
+    cooldown: f32,
+    pitch_variation: f32,
+}
+
+impl Sfx {
+    fn profile(&self) -> SfxProfile {
+        let sfx = |asset, max_voices, length, cooldown, pitch_variation| SfxProfile {
+            asset,
+            category: AudioCategory::Sfx,
+            volume: 1.0,
+            max_voices,
+            length,
+            cooldown,
+            pitch_variation,
+        };
+
+        match self {
+            Sfx::Slice => sfx("sounds/knifeSlice.ogg", 4, 0.3, 0.05, 0.15),
+            Sfx::Explosion => sfx("sounds/impactSoft_heavy_000.ogg", 2, 0.6, 0.1, 0.1),
+            Sfx::Coin => sfx("sounds/coin.wav", 3, 0.3, 0.04, 0.1),
+            Sfx::PowerUp => sfx("sounds/QuickBlade.ogg", 1, 0.5, 0.2, 0.05),
+            Sfx::Potion => sfx("sounds/slime_000.ogg", 2, 0.4, 0.1, 0.1),
+            Sfx::PropBreak => sfx("sounds/impactSoft_medium_004.ogg", 3, 0.4, 0.05, 0.15),
+            Sfx::GameOver => SfxProfile {
+                volume: 0.9,
+                ..sfx("sounds/you_lose.ogg", 1, 3.0, 3.0, 0.0)
+            },
+            Sfx::EnemyAttack => SfxProfile {
+                volume: 0.4,
+                ..sfx("sounds/scream.mp3", 1, 0.8, 0.6, 0.2)
+            },
+            Sfx::EnemyDeath => SfxProfile {
+                volume: 0.6,
+                ..sfx("sounds/impactSoft_heavy_000.ogg", 4, 0.3, 0.03, 0.2)
+            },
+            Sfx::MenuSelect => SfxProfile {
+                category: AudioCategory::Ui,
+                volume: 0.5,
+                ..sfx("sounds/impactSoft_medium_004.ogg", 2, 0.2, 0.05, 0.05)
+            },
+        }
+    }
+}
+
+/// Returns the pan (-1 left, 1 right) and volume multiplier for a sound at `position`,
+/// or `None` when it is too far away to be heard.
+fn spatialize(position: Vec2, listener: Vec2, half_extent: Vec2) -> Option<(f32, f32)> {
+    let relative = (position - listener) / half_extent.max(Vec2::splat(f32::EPSILON));
+    let distance = relative.length();
+    if distance > AUDIBLE_SCREENS {
+        return None;
+    }
+    let fade = ((distance - 1.0) / (AUDIBLE_SCREENS - 1.0)).clamp(0.0, 1.0);
+    let attenuation = 1.0 - fade * (1.0 - MIN_ATTENUATION);
+    Some((relative.x.clamp(-1.0, 1.0) * MAX_PAN, attenuation))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_sfx(
+    mut events: EventReader<PlaySfx>,
+    mut voices: ResMut<SfxVoices>,
+    mut global_rng: ResMut<GlobalRng>,
+    camera: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    audio: Res<Audio>,
+    assets: Res<AssetServer>,
+    settings: Res<UserSettings>,
+    time: Res<Time>,
+) {
+    let listener = camera.get_single().ok().map(|(transform, projection)| {
+        let area = projection.area;
+        (transform.translation().truncate(), area.half_size())
+    });
+
+    let now = time.elapsed_seconds_f64();
+    for PlaySfx { sfx, position } in events.iter() {
+        let spatial = match (position, listener) {
+            (Some(position), Some((listener, half_extent))) => {
+                match spatialize(*position, listener, half_extent) {
+                    Some(spatial) => Some(spatial),
+                    None => continue,
+                }
+            }
+            _ => None,
+        };
+
+        let profile = sfx.profile();
+        let recent = voices.0.entry(*sfx).or_default();
+        recent.retain(|started| now - started < profile.length as f64);
+        if recent.len() >= profile.max_voices
+            || recent
+                .last()
+                .is_some_and(|last| now - last < profile.cooldown as f64)
+        {
+            continue;
+        }
+        recent.push(now);
+
+        let pitch = 1.0 + global_rng.f32_normalized() * profile.pitch_variation;
+        let volume = profile.volume * settings.volume(profile.category);
+        let source = assets.load(profile.asset);
+        match spatial {
+            Some((pan, attenuation)) => {
+                audio.play_spatial_with_settings(
+                    source,
+                    PlaybackSettings {
+                        repeat: false,
+                        volume: volume * attenuation,
+                        speed: pitch,
+                    },
+                    Transform::IDENTITY,
+                    EAR_GAP,
+                    Vec3::new(pan, 0.0, 0.0),
+                );
+            }
+            None => {
+                audio.play_with_settings(
+                    source,
+                    PlaybackSettings {
+                        repeat: false,
+                        volume,
+                        speed: pitch,
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn menu_select_sfx(mut activated: EventReader<MenuActivated>, mut sfx: EventWriter<PlaySfx>) {
+    if activated.iter().count() > 0 {
+        sfx.send(PlaySfx::global(Sfx::MenuSelect));
+    }
+}
+
+fn enemy_death_sfx(mut killed: EventReader<EnemyKilled>, mut sfx: EventWriter<PlaySfx>) {
+    for EnemyKilled { position, .. } in killed.iter() {
+        sfx.send(PlaySfx::at(Sfx::EnemyDeath, *position));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_EXTENT: Vec2 = Vec2::new(10.0, 6.0);
+
+    #[test]
+    fn on_screen_sounds_play_at_full_volume() {
+        let (pan, attenuation) = spatialize(Vec2::new(0.0, 3.0), Vec2::ZERO, HALF_EXTENT).unwrap();
+        assert_eq!(pan, 0.0);
+        assert_eq!(attenuation, 1.0);
+    }
+
+    #[test]
+    fn off_screen_sounds_pan_towards_their_side_and_fade() {
+        let (pan, attenuation) =
+            spatialize(Vec2::new(-15.0, 0.0), Vec2::ZERO, HALF_EXTENT).unwrap();
+        assert_eq!(pan, -MAX_PAN);
+        assert!(attenuation < 1.0 && attenuation > MIN_ATTENUATION);
+        assert!(spatialize(Vec2::new(-30.0, 0.0), Vec2::ZERO, HALF_EXTENT).is_none());
+    }
+}
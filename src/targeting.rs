@@ -0,0 +1,182 @@
+use crate::prelude::*;
+use bevy::ecs::system::SystemParam;
+
+pub struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyGrid>()
+            .add_system(rebuild_enemy_grid.in_set(OnUpdate(GameState::GamePlay)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetingStrategy {
+    Nearest,
+    LowestHealth,
+    RandomInRange,
+    Strongest,
+}
+
+impl EnemyGrid {
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn insert(&mut self, candidate: TargetCandidate) {
+        let cell = self.cell(candidate.position);
+        self.cells.entry(cell).or_default().push(candidate);
+    }
+
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = &TargetCandidate> {
+        let min = self.cell(center - Vec2::splat(radius));
+        let max = self.cell(center + Vec2::splat(radius));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |candidate| candidate.position.distance(center) <= radius)
+    }
+}
+
+impl TargetingStrategy {
+    pub fn select<'a>(
+        &self,
+        origin: Vec2,
+        candidates: impl Iterator<Item = &'a TargetCandidate>,
+        global_rng: &mut GlobalRng,
+    ) -> Option<TargetCandidate> {
+        let distance = |candidate: &&TargetCandidate| candidate.position.distance_squared(origin);
+        match self {
+            TargetingStrategy::Nearest => {
+                candidates.min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            }
+            TargetingStrategy::LowestHealth => candidates.min_by(|a, b| {
+                a.health
+                    .total_cmp(&b.health)
+                    .then(distance(a).total_cmp(&distance(b)))
+            }),
+            TargetingStrategy::Strongest => candidates.max_by(|a, b| {
+                a.max_health
+                    .total_cmp(&b.max_health)
+                    .then(distance(b).total_cmp(&distance(a)))
+            }),
+            TargetingStrategy::RandomInRange => {
+                let candidates: Vec<&TargetCandidate> = candidates.collect();
+                if candidates.is_empty() {
+                    return None;
+                }
+                Some(candidates[global_rng.usize(0..candidates.len())])
+            }
+        }
+        .copied()
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Targeting<'w> {
+    grid: Res<'w, EnemyGrid>,
+    global_rng: ResMut<'w, GlobalRng>,
+}
+
+impl<'w> Targeting<'w> {
+    pub fn pick(
+        &mut self,
+        strategy: TargetingStrategy,
+        origin: Vec2,
+        range: f32,
+    ) -> Option<TargetCandidate> {
+        strategy.select(
+            origin,
+            self.grid.within(origin, range),
+            &mut self.global_rng,
+        )
+    }
+}
+
+pub fn rebuild_enemy_grid(
+    mut grid: ResMut<EnemyGrid>,
+    enemies: Query<(Entity, &GlobalTransform, &Enemy)>,
+) {
+    grid.cells.values_mut().for_each(Vec::clear);
+    for (entity, transform, enemy) in &enemies {
+        if enemy.health <= 0.0 {
+            continue;
+        }
+        grid.insert(TargetCandidate {
+            entity,
+            position: transform.translation().truncate(),
+            health: enemy.health,
+            max_health: enemy.max_health,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(enemies: &[(f32, f32, f32, f32)]) -> EnemyGrid {
+        let mut grid = EnemyGrid::default();
+        for (index, (x, y, health, max_health)) in enemies.iter().enumerate() {
+            grid.insert(TargetCandidate {
+                entity: Entity::from_raw(index as u32),
+                position: Vec2::new(*x, *y),
+                health: *health,
+                max_health: *max_health,
+            });
+        }
+        grid
+    }
+
+    fn pick(grid: &EnemyGrid, strategy: TargetingStrategy, range: f32) -> Option<u32> {
+        strategy
+            .select(
+                Vec2::ZERO,
+                grid.within(Vec2::ZERO, range),
+                &mut GlobalRng::with_seed(7),
+            )
+            .map(|candidate| candidate.entity.index())
+    }
+
+    #[test]
+    fn within_spans_cells_and_respects_radius() {
+        let grid = grid(&[
+            (1.0, 1.0, 5.0, 5.0),
+            (-5.0, 3.0, 5.0, 5.0),
+            (9.0, 0.0, 5.0, 5.0),
+        ]);
+        let mut found: Vec<u32> = grid
+            .within(Vec2::ZERO, 6.0)
+            .map(|candidate| candidate.entity.index())
+            .collect();
+        found.sort();
+        assert_eq!(found, vec![0, 1]);
+    }
+
+    #[test]
+    fn strategies_pick_expected_enemy() {
+        let grid = grid(&[
+            (1.0, 0.0, 10.0, 10.0),
+            (3.0, 0.0, 2.0, 30.0),
+            (5.0, 0.0, 8.0, 15.0),
+        ]);
+        assert_eq!(pick(&grid, TargetingStrategy::Nearest, 10.0), Some(0));
+        assert_eq!(pick(&grid, TargetingStrategy::LowestHealth, 10.0), Some(1));
+        assert_eq!(pick(&grid, TargetingStrategy::Strongest, 10.0), Some(1));
+        assert_eq!(pick(&grid, TargetingStrategy::Strongest, 2.0), Some(0));
+    }
+
+    #[test]
+    fn nothing_in_range_has_no_target() {
+        let grid = grid(&[(20.0, 0.0, 10.0, 10.0)]);
+        for strategy in [
+            TargetingStrategy::Nearest,
+            TargetingStrategy::LowestHealth,
+            TargetingStrategy::RandomInRange,
+            TargetingStrategy::Strongest,
+        ] {
+            assert_eq!(pick(&grid, strategy, 10.0), None);
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::prelude::*;
+use crate::{
+    camera::WorldToScreen,
+    menu::{menu_button, menu_text},
+    prelude::*,
+};
 
 pub struct GameUiPlugin;
 
@@ -9,95 +13,28 @@ impl Plugin for GameUiPlugin {
             .add_system(despawn_main_menu_ui.in_schedule(OnExit(GameState::MainMenu)))
             .add_system(spawn_game_over_ui.in_schedule(OnEnter(GameState::GameOver)))
             .add_system(despawn_game_over_ui.in_schedule(OnExit(GameState::GameOver)))
-            .add_system(about_button_system)
-            .add_system(about_back_button_system)
+            .add_system(menu_actions)
             .add_system(show_about_ui)
             .add_startup_system(spawn_about_ui)
-            .add_system(start_button_system)
-            .add_system(game_over_button_system)
-            .add_system(update_world_text)
             .insert_resource(AboutShown(false))
-            .add_system(player_health_ui_sync.in_set(OnUpdate(GameState::GamePlay)));
+            .add_systems(
+                (player_health_ui_sync, player_ui_follow).in_set(OnUpdate(GameState::GamePlay)),
+            );
     }
 }
 
-fn start_button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (With<Button>, With<StartButtonUI>),
-    >,
-    about: Res<AboutShown>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if about.0 {
-        return;
-    }
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Clicked => {
-                *color = Color::RED.into();
-                next_state.set(GameState::GamePlay);
-            }
-            Interaction::Hovered => {
-                *color = Color::GREEN.into();
-            }
-            Interaction::None => {
-                *color = Color::DARK_GREEN.into();
-            }
-        }
-    }
-}
-
-fn about_button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (With<Button>, With<AboutButtonUI>),
-    >,
-    mut about: ResMut<AboutShown>,
-    _next_state: ResMut<NextState<GameState>>,
-) {
-    if about.0 {
-        return;
-    }
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Clicked => {
-                *color = Color::RED.into();
-                about.0 = true;
-            }
-            Interaction::Hovered => {
-                *color = Color::GREEN.into();
-            }
-            Interaction::None => {
-                *color = Color::DARK_GREEN.into();
-            }
-        }
-    }
-}
-
-fn about_back_button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (With<Button>, With<AboutBackButton>),
-    >,
+fn menu_actions(
+    mut activated: EventReader<MenuActivated>,
     mut about: ResMut<AboutShown>,
-    _next_state: ResMut<NextState<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if !about.0 {
-        return;
-    }
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Clicked => {
-                *color = Color::RED.into();
-                about.0 = false;
-            }
-            Interaction::Hovered => {
-                *color = Color::GREEN.into();
-            }
-            Interaction::None => {
-                *color = Color::DARK_GREEN.into();
-            }
+    for MenuActivated(action) in activated.iter() {
+        match action {
+            MenuAction::StartGame => next_state.set(GameState::GamePlay),
+            MenuAction::ShowAbout => about.0 = true,
+            MenuAction::CloseAbout => about.0 = false,
+            MenuAction::BackToMenu => next_state.set(GameState::MainMenu),
+            _ => {}
         }
     }
 }
@@ -112,29 +49,6 @@ fn show_about_ui(mut about: Query<&mut Visibility, With<AboutUI>>, about_shown:
     }
 }
 
-fn game_over_button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (With<Button>, With<GameOverButtonUI>),
-    >,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Clicked => {
-                *color = Color::RED.into();
-                next_state.set(GameState::MainMenu);
-            }
-            Interaction::Hovered => {
-                *color = Color::GREEN.into();
-            }
-            Interaction::None => {
-                *color = Color::DARK_GREEN.into();
-            }
-        }
-    }
-}
-
 fn despawn_main_menu_ui(mut commands: Commands, ui: Query<Entity, With<MainMenuUI>>) {
     for ui in &ui {
         commands.entity(ui).despawn_recursive();
@@ -147,13 +61,16 @@ fn despawn_game_over_ui(mut commands: Commands, ui: Query<Entity, With<GameOverU
     }
 }
 
-fn spawn_main_menu_ui(mut commands: Commands, assets: Res<AssetServer>) {
-    let font = assets.load("fonts/dos.ttf");
-
+fn spawn_main_menu_ui(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    localizer: Localizer,
+    theme: Res<MenuTheme>,
+) {
     let menu_parent = (
         NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(38.0), Val::Percent(32.0)),
+                size: Size::new(Val::Percent(38.0), Val::Percent(42.0)),
                 position: UiRect {
                     left: Val::Percent(47.0),
                     right: Val::Auto,
@@ -171,63 +88,20 @@ fn spawn_main_menu_ui(mut commands: Commands, assets: Res<AssetServer>) {
         MainMenuUI,
     );
 
-    let button = (
-        ButtonBundle {
-            style: Style {
-                size: Size::new(Val::Percent(70.0), Val::Percent(30.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                align_self: AlignSelf::Center,
-                ..default()
-            },
-
-            background_color: Color::CRIMSON.into(),
-            ..default()
-        },
-        StartButtonUI,
-    );
-
-    let button_text = TextBundle::from_section(
-        "Start Game!",
-        TextStyle {
-            font: font.clone(),
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    );
-
-    let button_2 = (
-        ButtonBundle {
-            style: Style {
-                size: Size::new(Val::Percent(70.0), Val::Percent(30.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                align_self: AlignSelf::Center,
-                ..default()
-            },
-
-            background_color: Color::CRIMSON.into(),
-            ..default()
-        },
-        AboutButtonUI,
-    );
-
-    let button_text_2 = TextBundle::from_section(
-        "About",
-        TextStyle {
-            font,
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    );
+    let button_size = Size::new(Val::Percent(70.0), Val::Percent(28.0));
 
     commands.spawn(menu_parent).with_children(|commands| {
-        commands.spawn(button).with_children(|commands| {
-            commands.spawn(button_text);
-        });
-        commands.spawn(button_2).with_children(|commands| {
-            commands.spawn(button_text_2);
-        });
+        for (action, text) in [
+            (MenuAction::StartGame, "menu.start"),
+            (MenuAction::ShowAbout, "menu.about"),
+            (MenuAction::OpenSettings, "menu.settings"),
+        ] {
+            commands
+                .spawn(menu_button(&theme, action, MENU_LAYER, button_size))
+                .with_children(|commands| {
+                    commands.spawn(menu_text(&theme, &localizer, text, 40.0));
+                });
+        }
     });
 
     commands.spawn((
@@ -247,9 +121,7 @@ fn spawn_main_menu_ui(mut commands: Commands, assets: Res<AssetServer>) {
     ));
 }
 
-fn spawn_game_over_ui(mut commands: Commands, assets: Res<AssetServer>) {
-    let font = assets.load("fonts/dos.ttf");
-
+fn spawn_game_over_ui(mut commands: Commands, localizer: Localizer, theme: Res<MenuTheme>) {
     let menu_parent = (
         NodeBundle {
             style: Style {
@@ -278,39 +150,16 @@ fn spawn_game_over_ui(mut commands: Commands, assets: Res<AssetServer>) {
         ..default()
     };
 
-    let button = (
-        ButtonBundle {
-            style: Style {
-                size: Size::new(Val::Percent(50.0), Val::Percent(15.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                align_self: AlignSelf::Center,
-                ..default()
-            },
-
-            background_color: Color::CRIMSON.into(),
-            ..default()
-        },
-        GameOverButtonUI,
+    let button = menu_button(
+        &theme,
+        MenuAction::BackToMenu,
+        MENU_LAYER,
+        Size::new(Val::Percent(50.0), Val::Percent(15.0)),
     );
 
-    let title_text = TextBundle::from_section(
-        "Game Over!",
-        TextStyle {
-            font: font.clone(),
-            font_size: 64.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    );
+    let title_text = localizer.localized("game_over.title", 64.0, Color::rgb(0.9, 0.9, 0.9));
 
-    let button_text = TextBundle::from_section(
-        "Back to Menu",
-        TextStyle {
-            font,
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    );
+    let button_text = menu_text(&theme, &localizer, "menu.back_to_menu", 40.0);
 
     commands.spawn(menu_parent).with_children(|commands| {
         commands.spawn(menu_title).with_children(|commands| {
@@ -322,9 +171,7 @@ fn spawn_game_over_ui(mut commands: Commands, assets: Res<AssetServer>) {
     });
 }
 
-fn spawn_about_ui(mut commands: Commands, assets: Res<AssetServer>) {
-    let font = assets.load("fonts/dos.ttf");
-
+fn spawn_about_ui(mut commands: Commands, localizer: Localizer, theme: Res<MenuTheme>) {
     let about_parent = (
         NodeBundle {
             style: Style {
@@ -354,82 +201,44 @@ fn spawn_about_ui(mut commands: Commands, assets: Res<AssetServer>) {
         ..default()
     };
 
-    let button = (
-        ButtonBundle {
-            style: Style {
-                size: Size::new(Val::Percent(50.0), Val::Percent(15.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                align_self: AlignSelf::Center,
-                ..default()
-            },
-
-            background_color: Color::CRIMSON.into(),
-            ..default()
-        },
-        AboutBackButton,
+    let button = menu_button(
+        &theme,
+        MenuAction::CloseAbout,
+        ABOUT_LAYER,
+        Size::new(Val::Percent(50.0), Val::Percent(15.0)),
     );
 
     let font_size = 24.0;
 
-    let text = vec![
-        TextSection {
-            value: "BevyQuest".to_string(),
-            style: TextStyle {
-                font: font.clone(),
-                font_size,
-                color: Color::DARK_GREEN,
-            },
-        },
-        TextSection {
-            value: " is a action rougelike game designed for my wife".to_string(),
-            style: TextStyle {
-                font: font.clone(),
-                font_size,
-                color: Color::CRIMSON,
-            },
-        },
-        TextSection {
-            value: " Trisha".to_string(),
-            style: TextStyle {
-                font: font.clone(),
-                font_size,
-                color: Color::TEAL,
-            },
-        },
-
-        TextSection {
-            value: "\n\nAll assets were sourced from kenney.nl or purchased for use.\n\nMuch of the code is structured around work done by  the Matthew Bryant (LogicProjects on Youtube).".to_string(),
-            style: TextStyle {
-                font: font.clone(),
-                font_size,
-                color: Color::CRIMSON,
-            },
-        },
+    let sections = [
+        ("about.name", Color::DARK_GREEN),
+        ("about.tagline", Color::CRIMSON),
+        ("about.dedication", Color::TEAL),
+        ("about.credits", Color::CRIMSON),
     ];
 
-    let mut title_text = TextBundle::from_sections(text);
+    let text = sections
+        .iter()
+        .map(|(key, color)| {
+            let value = localizer.text(key);
+            TextSection {
+                style: localizer.style(&value, font_size, *color),
+                value,
+            }
+        })
+        .collect::<Vec<_>>();
 
-    // https://github.com/bevyengine/bevy/issues/1490
-    title_text.style.size.width = Val::Px(WIDTH * 0.90);
-
-    let button_text = TextBundle::from_section(
-        "Back to Menu",
-        TextStyle {
-            font: font.clone(),
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
+    let mut title_text = (
+        TextBundle::from_sections(text),
+        LocalizedText(sections.iter().map(|(key, _)| *key).collect()),
     );
 
-    let main_title_text = TextBundle::from_section(
-        "About BevyQuest",
-        TextStyle {
-            font,
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    );
+    // https://github.com/bevyengine/bevy/issues/1490
+    title_text.0.style.size.width = Val::Px(WIDTH * 0.90);
+
+    let button_text = menu_text(&theme, &localizer, "menu.back_to_menu", 40.0);
+
+    let main_title_text = localizer.localized("about.title", 40.0, Color::rgb(0.9, 0.9, 0.9));
 
     commands.spawn(about_parent).with_children(|commands| {
         commands.spawn(about_box).with_children(|commands| {
@@ -450,6 +259,29 @@ fn player_health_ui_sync(mut ui: Query<&mut Style, With<HealthUI>>, player: Quer
     style.size.width = Val::Percent(percent * 100.0);
 }
 
+fn player_ui_follow(
+    mut ui: Query<&mut Style, With<PlayerUI>>,
+    player: Query<&GlobalTransform, With<Player>>,
+    world_to_screen: WorldToScreen,
+) {
+    let (Ok(mut style), Ok(player)) = (ui.get_single_mut(), player.get_single()) else {
+        return;
+    };
+
+    let viewport = world_to_screen.viewport();
+    let size = Vec2::new(viewport.width() * 0.05, viewport.height() * 0.02);
+    style.size = Size::new(Val::Px(size.x), Val::Px(size.y));
+
+    if let Some(coords) = world_to_screen.project(player.translation() - Vec3::Y * 1.6) {
+        style.position = UiRect {
+            left: Val::Px(coords.x - size.x / 2.0),
+            right: Val::Auto,
+            top: Val::Px(coords.y),
+            bottom: Val::Auto,
+        };
+    }
+}
+
 fn spawn_player_ui(mut commands: Commands) {
     let parent_node = (
         NodeBundle {
@@ -492,75 +324,3 @@ fn spawn_player_ui(mut commands: Commands) {
         commands.spawn(health_node);
     });
 }
-
-fn update_world_text(
-    mut commands: Commands,
-    mut text: Query<(Entity, &mut Style, &mut WorldTextUI)>,
-    main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
-    render_camera: Query<&Camera, With<FinalCamera>>,
-    time: Res<Time>,
-) {
-    //AHHH
-    let (camera, transform) = main_camera.single();
-    let final_camera = render_camera.single();
-
-    for (entity, mut style, mut world_ui) in &mut text {
-        world_ui.lifetime.tick(time.delta());
-        if world_ui.lifetime.just_finished() {
-            commands.entity(entity).despawn_recursive();
-        }
-
-        world_ui.position = world_ui.position + world_ui.velocity * time.delta_seconds();
-
-        if let Some(coords) = camera.world_to_viewport(transform, world_ui.position.extend(0.0)) {
-            let mut coords = coords / Vec2::new(RENDER_WIDTH, RENDER_HEIGHT)
-                * final_camera.logical_viewport_size().unwrap();
-            coords.y = final_camera.logical_viewport_size().unwrap().y - coords.y;
-
-            style.position = UiRect {
-                top: Val::Px(coords.y),
-                left: Val::Px(coords.x),
-                bottom: Val::Px(coords.y),
-                right: Val::Px(coords.x),
-            }
-        }
-    }
-}
-
-pub fn spawn_world_text(commands: &mut Commands, assets: &AssetServer, position: Vec2, text: &str) {
-    let font = assets.load("fonts/dos.ttf");
-
-    let position = position + Vec2::new(-0.2, 1.4);
-
-    let parent = (
-        NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(20.0), Val::Percent(20.0)),
-                position_type: PositionType::Absolute,
-                align_items: AlignItems::FlexStart,
-                justify_content: JustifyContent::FlexStart,
-                ..default()
-            },
-            z_index: ZIndex::Global(-100),
-            ..default()
-        },
-        WorldTextUI {
-            lifetime: Timer::from_seconds(0.5, TimerMode::Once),
-            velocity: Vec2::new(0.15, 1.5),
-            position,
-        },
-    );
-
-    let text = TextBundle::from_section(
-        text,
-        TextStyle {
-            font,
-            font_size: 32.0,
-            color: Color::rgb(0.95, 0.2, 0.2),
-        },
-    );
-
-    commands.spawn(parent).with_children(|commands| {
-        commands.spawn(text);
-    });
-}